@@ -28,6 +28,20 @@ mod edwards_benches {
         c.bench_function("EdwardsPoint compression", move |b| b.iter(|| B.compress()));
     }
 
+    fn compress_batch(c: &mut Criterion) {
+        c.bench_function_over_inputs(
+            "Batch EdwardsPoint compression",
+            |b, &&size| {
+                let mut rng = thread_rng();
+                let points: Vec<EdwardsPoint> = (0..size)
+                    .map(|_| &Scalar::random(&mut rng) * &constants::ED25519_BASEPOINT_TABLE)
+                    .collect();
+                b.iter(|| EdwardsPoint::compress_batch(&points));
+            },
+            &BATCH_SIZES,
+        );
+    }
+
     fn decompress(c: &mut Criterion) {
         let B_comp = &constants::ED25519_BASEPOINT_COMPRESSED;
         c.bench_function("EdwardsPoint decompression", move |b| {
@@ -35,6 +49,13 @@ mod edwards_benches {
         });
     }
 
+    fn is_torsion_free(c: &mut Criterion) {
+        let B = &constants::ED25519_BASEPOINT_POINT;
+        c.bench_function("EdwardsPoint torsion-free check", move |b| {
+            b.iter(|| B.is_torsion_free())
+        });
+    }
+
     fn consttime_fixed_base_scalar_mul(c: &mut Criterion) {
         let B = &constants::ED25519_BASEPOINT_TABLE;
         let s = Scalar::from(897987897u64).invert();
@@ -51,6 +72,19 @@ mod edwards_benches {
         });
     }
 
+    fn vartime_precomputed_base_scalar_mul(c: &mut Criterion) {
+        c.bench_function("Variable-time precomputed-base scalar mul", |bench| {
+            let mut rng = thread_rng();
+            let A = &Scalar::random(&mut rng) * &constants::ED25519_BASEPOINT_TABLE;
+            let A_table = edwards::VartimeEdwardsPrecomputedBase::new(&A);
+            bench.iter_batched(
+                || Scalar::random(&mut rng),
+                |s| A_table.mul(&s),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+
     fn vartime_double_base_scalar_mul(c: &mut Criterion) {
         c.bench_function("Variable-time aA+bB, A variable, B fixed", |bench| {
             let mut rng = thread_rng();
@@ -63,15 +97,32 @@ mod edwards_benches {
         });
     }
 
+    fn vartime_double_scalar_mul(c: &mut Criterion) {
+        c.bench_function("Variable-time aA+bB, A and B variable", |bench| {
+            let mut rng = thread_rng();
+            let A = &Scalar::random(&mut rng) * &constants::ED25519_BASEPOINT_TABLE;
+            let B = &Scalar::random(&mut rng) * &constants::ED25519_BASEPOINT_TABLE;
+            bench.iter_batched(
+                || (Scalar::random(&mut rng), Scalar::random(&mut rng)),
+                |(a, b)| EdwardsPoint::vartime_double_scalar_mul(&a, &A, &b, &B),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+
     criterion_group! {
         name = edwards_benches;
         config = Criterion::default();
         targets =
         compress,
+        compress_batch,
         decompress,
+        is_torsion_free,
         consttime_fixed_base_scalar_mul,
         consttime_variable_base_scalar_mul,
+        vartime_precomputed_base_scalar_mul,
         vartime_double_base_scalar_mul,
+        vartime_double_scalar_mul,
     }
 }
 
@@ -79,6 +130,7 @@ mod multiscalar_benches {
     use super::*;
     use curve25519_dalek::edwards;
     use curve25519_dalek::edwards::EdwardsPoint;
+    use curve25519_dalek::edwards::MultiscalarMulAlgorithm;
     use curve25519_dalek::edwards::VartimeEdwardsPrecomputation;
     use curve25519_dalek::traits::MultiscalarMul;
     use curve25519_dalek::traits::VartimeMultiscalarMul;
@@ -136,6 +188,28 @@ mod multiscalar_benches {
         );
     }
 
+    fn vartime_pippenger_window_sweep(c: &mut Criterion) {
+        let size = 2048;
+        c.bench_function_over_inputs(
+            &format!("Variable-time Pippenger with window w ({} inputs)", size),
+            move |b, &&w| {
+                let points = construct_points(size);
+                b.iter_batched(
+                    || construct_scalars(size),
+                    |scalars| {
+                        EdwardsPoint::vartime_multiscalar_mul_with_params(
+                            MultiscalarMulAlgorithm::PippengerWithWindow(w),
+                            &scalars,
+                            &points,
+                        )
+                    },
+                    BatchSize::SmallInput,
+                );
+            },
+            &[4usize, 5, 6, 7, 8],
+        );
+    }
+
     fn vartime_precomputed_pure_static(c: &mut Criterion) {
         c.bench_function_over_inputs(
             "Variable-time fixed-base multiscalar multiplication",
@@ -218,6 +292,7 @@ mod multiscalar_benches {
         targets =
         consttime_multiscalar_mul,
         vartime_multiscalar_mul,
+        vartime_pippenger_window_sweep,
         vartime_precomputed_pure_static,
         vartime_precomputed_00_pct_dynamic,
         vartime_precomputed_20_pct_dynamic,
@@ -36,6 +36,14 @@ use std::path::Path;
 // For instance, this shouldn't exist here at all, but it does.
 #[cfg(feature = "serde")]
 extern crate serde;
+#[cfg(feature = "group")]
+extern crate group;
+#[cfg(feature = "group")]
+extern crate rand_core_06;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 
 // Macros come first!
 #[path = "src/macros.rs"]
@@ -63,10 +71,15 @@ mod traits;
 mod backend;
 #[path = "src/field.rs"]
 mod field;
+#[path = "src/hex.rs"]
+mod hex;
 #[path = "src/prelude.rs"]
 mod prelude;
 #[path = "src/window.rs"]
 mod window;
+#[cfg(feature = "serde")]
+#[path = "src/serde_hex.rs"]
+mod serde_hex;
 
 use edwards::EdwardsBasepointTable;
 
@@ -146,6 +146,7 @@ use traits::ValidityCheck;
 /// More details on the relationships between the different curve models
 /// can be found in the module-level documentation.
 #[derive(Copy, Clone)]
+#[allow(missing_docs)]
 pub struct ProjectivePoint {
     pub X: FieldElement,
     pub Y: FieldElement,
@@ -188,6 +189,7 @@ pub struct AffineNielsPoint {
 /// More details on the relationships between the different curve models
 /// can be found in the module-level documentation.
 #[derive(Copy, Clone)]
+#[allow(missing_docs)]
 pub struct ProjectiveNielsPoint {
     pub Y_plus_X:  FieldElement,
     pub Y_minus_X: FieldElement,
@@ -347,6 +349,41 @@ impl CompletedPoint {
     }
 }
 
+impl AffineNielsPoint {
+    /// Decode an `AffineNielsPoint` from the canonical encodings of
+    /// \\(y+x\\), \\(y-x\\), and \\(2dxy\\), in that order,
+    /// together with the affine point it represents.
+    ///
+    /// `bytes` must be 96 bytes long, and `two_inv` must be
+    /// \\(1/2\\), so that callers decoding many points only need to
+    /// invert once.
+    ///
+    /// Returns `None` if a coordinate is not canonically encoded, or
+    /// if \\(2dxy\\) does not match \\(y+x\\) and \\(y-x\\).
+    /// This does not check that the point is on the curve.
+    pub(crate) fn from_bytes(bytes: &[u8], two_inv: &FieldElement) -> Option<(AffineNielsPoint, EdwardsPoint)> {
+        let mut coordinates = [FieldElement::zero(); 3];
+        for (fe, fe_bytes) in coordinates.iter_mut().zip(bytes.chunks(32)) {
+            let mut buf = [0u8; 32];
+            buf.copy_from_slice(fe_bytes);
+            *fe = FieldElement::from_bytes(&buf);
+            if fe.to_bytes() != buf {
+                return None;
+            }
+        }
+        let [y_plus_x, y_minus_x, xy2d] = coordinates;
+
+        let X = &(&y_plus_x - &y_minus_x) * two_inv;
+        let Y = &(&y_plus_x + &y_minus_x) * two_inv;
+        let T = &X * &Y;
+        if xy2d != &T * &constants::EDWARDS_D2 {
+            return None;
+        }
+
+        Some((AffineNielsPoint { y_plus_x, y_minus_x, xy2d }, EdwardsPoint { X, Y, Z: FieldElement::one(), T }))
+    }
+}
+
 // ------------------------------------------------------------------------
 // Doubling
 // ------------------------------------------------------------------------
@@ -18,9 +18,10 @@
 
 pub mod variable_base;
 
-#[cfg(feature = "stage2_build")]
 pub mod vartime_double_base;
 
+pub mod straus_array;
+
 #[cfg(feature = "alloc")]
 pub mod straus;
 
@@ -12,9 +12,12 @@
 #![allow(non_snake_case)]
 
 use core::borrow::Borrow;
+use core::cmp;
 
+use backend::serial::curve_models::ProjectiveNielsPoint;
 use edwards::EdwardsPoint;
 use scalar::Scalar;
+use traits::Identity;
 use traits::VartimeMultiscalarMul;
 
 #[allow(unused_imports)]
@@ -61,7 +64,6 @@ use prelude::*;
 /// This algorithm is adapted from section 4 of https://eprint.iacr.org/2012/549.pdf.
 pub struct Pippenger;
 
-#[cfg(any(feature = "alloc", feature = "std"))]
 impl VartimeMultiscalarMul for Pippenger {
     type Point = EdwardsPoint;
 
@@ -71,48 +73,271 @@ impl VartimeMultiscalarMul for Pippenger {
         I::Item: Borrow<Scalar>,
         J: IntoIterator<Item = Option<EdwardsPoint>>,
     {
-        use traits::Identity;
+        PippengerScratch::default().optional_multiscalar_mul(scalars, points)
+    }
+}
+
+impl Pippenger {
+    /// Variable-time Pippenger with a fixed window width `w`, instead
+    /// of the width chosen from the number of inputs.
+    ///
+    /// The scalars are written in radix \\(2\^w\\), so there are
+    /// \\(\lceil 256/w \rceil\\) windows (one more when `w = 8`, for
+    /// the final carry), and in each window the points are sorted into
+    /// \\(2\^{w-1}\\) buckets.  Larger windows mean fewer windows, and
+    /// so fewer additions of points into buckets, but more buckets to
+    /// sum up in each window.
+    ///
+    /// # Panics
+    ///
+    /// If `w` is not between 4 and 8.
+    pub fn optional_multiscalar_mul_with_window<I, J>(
+        w: usize,
+        scalars: I,
+        points: J,
+    ) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
+    {
+        PippengerScratch::default().optional_multiscalar_mul_with_window(w, scalars, points)
+    }
+
+    /// Variable-time Pippenger for scalars which are all less than
+    /// \\(2\^b\\), where \\(b\\) is `max_bits`.
+    ///
+    /// The high radix-\\(2\^w\\) digits of such scalars are zero, so
+    /// only the windows holding the low \\(b\\) bits (and the carry out
+    /// of them) are computed.  This skips the bucket sums and the
+    /// doublings for the other windows.
+    ///
+    /// The bound is only checked in debug builds: if a scalar is not
+    /// less than \\(2\^b\\), the result is wrong.
+    pub fn optional_multiscalar_mul_with_max_bits<I, J>(
+        max_bits: usize,
+        scalars: I,
+        points: J,
+    ) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
+    {
+        PippengerScratch::default().optional_multiscalar_mul_with_max_bits(max_bits, scalars, points)
+    }
+
+    /// Variable-time Pippenger with window width `w`, making a single
+    /// pass over the inputs.
+    ///
+    /// Instead of storing the inputs and sorting them into buckets
+    /// one window at a time, this keeps the buckets for every window
+    /// at once, and sorts each point into all of them as it arrives.
+    /// The memory used is \\(\lceil 256/w \rceil \cdot 2\^{w-1}\\)
+    /// points (about 680KB for `w = 8`), independent of the number of
+    /// inputs, and the iterators are consumed exactly once.
+    ///
+    /// # Panics
+    ///
+    /// If `w` is not between 4 and 8, or if `scalars` and `points`
+    /// have different lengths.
+    pub fn optional_multiscalar_mul_streaming<I, J>(
+        w: usize,
+        scalars: I,
+        points: J,
+    ) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
+    {
+        assert!((4..=8).contains(&w), "Pippenger window width must be between 4 and 8");
+
+        let mut scalars = scalars.into_iter();
+        let mut points = points.into_iter();
+
+        let digits_count = Scalar::to_radix_2w_size_hint(w);
+        let buckets_count: usize = (1 << w) / 2;
+
+        // buckets[j*buckets_count + i] corresponds to a multiplication
+        // factor (i+1) in the j-th window.
+        let mut buckets: Vec<EdwardsPoint> = Vec::new();
+        buckets.resize(digits_count * buckets_count, EdwardsPoint::identity());
+
+        loop {
+            let (s, pt) = match (scalars.next(), points.next()) {
+                (Some(s), Some(maybe_p)) => (s, maybe_p?.to_projective_niels()),
+                (None, None) => break,
+                _ => panic!("scalars and points must have the same length"),
+            };
+            let digits = s.borrow().to_radix_2w(w);
+
+            for (window, &digit) in buckets.chunks_mut(buckets_count).zip(digits.iter()) {
+                // Widen digit so that we don't run into edge cases when w=8.
+                let digit = digit as i16;
+                if digit > 0 {
+                    let b = (digit - 1) as usize;
+                    window[b] = (&window[b] + &pt).to_extended();
+                } else if digit < 0 {
+                    let b = (-digit - 1) as usize;
+                    window[b] = (&window[b] - &pt).to_extended();
+                }
+            }
+        }
+
+        // Sum the buckets of each window as in `windowed_multiscalar_mul`.
+        let mut columns = buckets.chunks(buckets_count).rev().map(|window| {
+            let mut buckets_intermediate_sum = window[buckets_count - 1];
+            let mut buckets_sum = window[buckets_count - 1];
+            for bucket in window[..(buckets_count - 1)].iter().rev() {
+                buckets_intermediate_sum += bucket;
+                buckets_sum += buckets_intermediate_sum;
+            }
+
+            buckets_sum
+        });
+
+        let hi_column = columns.next().unwrap();
+
+        Some(columns.fold(hi_column, |total, p| total.mul_by_pow_2(w as u32) + p))
+    }
+}
+
+/// Reusable buffers for the Pippenger implementation.
+///
+/// Holding on to a `PippengerScratch` across calls lets repeated
+/// multiscalar multiplications of similar sizes reuse the same
+/// allocations for the scalar digits, points, and buckets.
+#[derive(Default)]
+pub struct PippengerScratch {
+    scalars_points: Vec<([i8; 64], ProjectiveNielsPoint)>,
+    buckets: Vec<EdwardsPoint>,
+}
+
+impl PippengerScratch {
+    /// Preallocate buffers for up to `size` point-scalar pairs.
+    pub fn with_capacity(size: usize) -> PippengerScratch {
+        PippengerScratch {
+            scalars_points: Vec::with_capacity(size),
+            // The largest window is w = 8, with 2^8/2 buckets.
+            buckets: Vec::with_capacity(128),
+        }
+    }
+
+    /// Variable-time Pippenger, using `self` for temporary storage.
+    ///
+    /// See the `VartimeMultiscalarMul` implementation for `Pippenger`.
+    pub fn optional_multiscalar_mul<I, J>(&mut self, scalars: I, points: J) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
+    {
+        let mut scalars = scalars.into_iter();
+        let size = scalars.by_ref().size_hint().0;
+        let w = PippengerScratch::window_width(size);
+
+        self.optional_multiscalar_mul_with_window(w, scalars, points)
+    }
 
+    /// Variable-time Pippenger for scalars less than \\(2\^b\\), where
+    /// \\(b\\) is `max_bits`, using `self` for temporary storage.
+    ///
+    /// See `Pippenger::optional_multiscalar_mul_with_max_bits`.
+    pub fn optional_multiscalar_mul_with_max_bits<I, J>(
+        &mut self,
+        max_bits: usize,
+        scalars: I,
+        points: J,
+    ) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
+    {
         let mut scalars = scalars.into_iter();
         let size = scalars.by_ref().size_hint().0;
+        let w = PippengerScratch::window_width(size);
+
+        self.windowed_multiscalar_mul(w, max_bits, scalars, points)
+    }
 
+    /// Choose the window width for `size` point-scalar pairs.
+    fn window_width(size: usize) -> usize {
         // Digit width in bits. As digit width grows,
         // number of point additions goes down, but amount of
         // buckets and bucket additions grows exponentially.
-        let w = if size < 500 {
+        if size < 500 {
             6
         } else if size < 800 {
             7
         } else {
             8
-        };
+        }
+    }
+
+    /// Variable-time Pippenger with window width `w`, using `self`
+    /// for temporary storage.
+    ///
+    /// See `Pippenger::optional_multiscalar_mul_with_window`.
+    pub fn optional_multiscalar_mul_with_window<I, J>(
+        &mut self,
+        w: usize,
+        scalars: I,
+        points: J,
+    ) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
+    {
+        self.windowed_multiscalar_mul(w, 256, scalars, points)
+    }
+
+    /// Variable-time Pippenger with window width `w`, for scalars less
+    /// than \\(2\^b\\), where \\(b\\) is `max_bits`.
+    fn windowed_multiscalar_mul<I, J>(
+        &mut self,
+        w: usize,
+        max_bits: usize,
+        scalars: I,
+        points: J,
+    ) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
+    {
+        assert!((4..=8).contains(&w), "Pippenger window width must be between 4 and 8");
+
+        let scalars = scalars.into_iter();
 
         let max_digit: usize = 1 << w;
-        let digits_count: usize = Scalar::to_radix_2w_size_hint(w);
+        // A scalar below 2^b has nonzero digits only in its first b/w + 1
+        // windows, plus possibly a carry of 1 into the next one.
+        let digits_count: usize = cmp::min(Scalar::to_radix_2w_size_hint(w), max_bits / w + 2);
         let buckets_count: usize = max_digit / 2; // digits are signed+centered hence 2^w/2, excluding 0-th bucket
 
+        let PippengerScratch {
+            ref mut scalars_points,
+            ref mut buckets,
+        } = *self;
+
         // Collect optimized scalars and points in buffers for repeated access
         // (scanning the whole set per digit position).
-        let scalars = scalars
-            .into_iter()
-            .map(|s| s.borrow().to_radix_2w(w));
-
-        let points = points
-            .into_iter()
-            .map(|p| p.map(|P| P.to_projective_niels()));
-
-        let scalars_points = scalars.zip(points).map(|(s,maybe_p)| maybe_p.map(|p| (s,p) ) )
-            .collect::<Option<Vec<_>>>();
-        let scalars_points = match scalars_points {
-            Some(sp) => sp,
-            None => return None,
-        };
+        scalars_points.clear();
+        for (s, maybe_p) in scalars.zip(points) {
+            match maybe_p {
+                Some(P) => scalars_points.push((s.borrow().to_radix_2w(w), P.to_projective_niels())),
+                None => return None,
+            }
+            debug_assert!(scalars_points.last().unwrap().0[digits_count..].iter().all(|&d| d == 0));
+        }
 
         // Prepare 2^w/2 buckets.
         // buckets[i] corresponds to a multiplication factor (i+1).
-        let mut buckets: Vec<_> = (0..buckets_count)
-            .map(|_| EdwardsPoint::identity())
-            .collect();
+        buckets.clear();
+        buckets.resize(buckets_count, EdwardsPoint::identity());
 
         let mut columns = (0..digits_count).rev().map(|digit_index| {
             // Clear the buckets when processing another digit.
@@ -203,4 +428,65 @@ mod test {
             n = n / 2;
         }
     }
+
+    #[test]
+    fn test_vartime_pippenger_streaming() {
+        let n = 64;
+        let x = Scalar::from(2128506u64).invert();
+        let y = Scalar::from(4443282u64).invert();
+        let points: Vec<_> = (0..n)
+            .map(|i| constants::ED25519_BASEPOINT_POINT * Scalar::from(1 + i as u64))
+            .collect();
+        let scalars: Vec<_> = (0..n)
+            .map(|i| x + (Scalar::from(i as u64) * y))
+            .collect();
+
+        let control = Pippenger::vartime_multiscalar_mul(&scalars, &points);
+
+        for w in 4..=8 {
+            let subject = Pippenger::optional_multiscalar_mul_streaming(
+                w,
+                scalars.iter(),
+                points.iter().map(|P| Some(*P)),
+            ).unwrap();
+
+            assert_eq!(subject.compress(), control.compress());
+        }
+
+        let empty = Pippenger::optional_multiscalar_mul_streaming(6, &[] as &[Scalar], None);
+        assert_eq!(empty, Some(EdwardsPoint::identity()));
+        assert!(Pippenger::optional_multiscalar_mul_streaming(6, &scalars[..1], Some(None)).is_none());
+    }
+
+    #[test]
+    fn test_vartime_pippenger_with_max_bits() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let points: Vec<_> = (0..8u64)
+            .map(|i| B * Scalar::from(3 + i))
+            .collect();
+
+        for w in 4..=8 {
+            for &bits in [0usize, 1, w - 1, w, w + 1, 63, 64, 100, 128].iter() {
+                // Scalars with all of the low `bits` bits set have the
+                // longest carry chains.
+                let mut bytes = [0u8; 32];
+                for i in 0..bits {
+                    bytes[i / 8] |= 1 << (i % 8);
+                }
+                let max = Scalar::from_bits(bytes);
+                bytes[0] &= 0xfe;
+                let even = Scalar::from_bits(bytes);
+                let scalars: Vec<_> = (0..8)
+                    .map(|i| if i % 2 == 0 { max } else { even })
+                    .collect();
+
+                let control: EdwardsPoint = scalars.iter().zip(points.iter()).map(|(c, P)| P * c).sum();
+                let subject = PippengerScratch::default()
+                    .windowed_multiscalar_mul(w, bits, &scalars, points.iter().map(|P| Some(*P)))
+                    .unwrap();
+
+                assert_eq!(subject.compress(), control.compress());
+            }
+        }
+    }
 }
@@ -17,8 +17,10 @@ use backend::serial::curve_models::{
     AffineNielsPoint, CompletedPoint, ProjectiveNielsPoint, ProjectivePoint,
 };
 use edwards::EdwardsPoint;
+use field::FieldElement;
 use scalar::Scalar;
 use traits::Identity;
+use traits::ValidityCheck;
 use traits::VartimePrecomputedMultiscalarMul;
 use window::{NafLookupTable5, NafLookupTable8};
 
@@ -112,3 +114,71 @@ impl VartimePrecomputedMultiscalarMul for VartimePrecomputedStraus {
         Some(S.to_extended())
     }
 }
+
+/// The length of the encoding of one static point's lookup table:
+/// 64 `AffineNielsPoint`s of three field elements each.
+const TABLE_BYTES: usize = 64 * 3 * 32;
+
+impl VartimePrecomputedStraus {
+    /// Encode the static lookup tables as bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.static_lookup_tables.len() * TABLE_BYTES);
+        for table in self.static_lookup_tables.iter() {
+            for P in table.0.iter() {
+                bytes.extend_from_slice(&P.y_plus_x.to_bytes());
+                bytes.extend_from_slice(&P.y_minus_x.to_bytes());
+                bytes.extend_from_slice(&P.xy2d.to_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Decode and validate static lookup tables encoded by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let table_chunks = bytes.chunks_exact(TABLE_BYTES);
+        if !table_chunks.remainder().is_empty() {
+            return None;
+        }
+
+        let two_inv = FieldElement::from_u64(2).invert();
+        let static_lookup_tables = table_chunks
+            .map(|table_bytes| table_from_bytes(table_bytes, &two_inv))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self { static_lookup_tables })
+    }
+}
+
+/// Decode the lookup table of one static point, checking that it
+/// holds the odd multiples \\(A, 3A, \ldots, 127A\\) of some curve
+/// point \\(A\\).
+///
+/// Each entry is compared against the sum of the previous entry and
+/// \\([2]A\\) in projective coordinates, so this needs no inversions,
+/// unlike building the table from scratch.
+fn table_from_bytes(bytes: &[u8], two_inv: &FieldElement) -> Option<NafLookupTable8<AffineNielsPoint>> {
+    let mut entries = [AffineNielsPoint::identity(); 64];
+    let mut points = [EdwardsPoint::identity(); 64];
+
+    for (i, entry_bytes) in bytes.chunks(96).enumerate() {
+        let (entry, point) = AffineNielsPoint::from_bytes(entry_bytes, two_inv)?;
+        entries[i] = entry;
+        points[i] = point;
+    }
+
+    let A = points[0];
+    if !A.is_valid() {
+        return None;
+    }
+
+    let A2 = A.double();
+    for i in 0..63 {
+        let expected = (&A2 + &entries[i]).to_extended();
+        let P = &points[i + 1];
+        if expected.X != &P.X * &expected.Z || expected.Y != &P.Y * &expected.Z {
+            return None;
+        }
+    }
+
+    Some(NafLookupTable8(entries))
+}
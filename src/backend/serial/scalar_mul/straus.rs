@@ -14,10 +14,13 @@
 
 use core::borrow::Borrow;
 
+use backend::serial::curve_models::{CompletedPoint, ProjectiveNielsPoint, ProjectivePoint};
 use edwards::EdwardsPoint;
 use scalar::Scalar;
+use traits::Identity;
 use traits::MultiscalarMul;
 use traits::VartimeMultiscalarMul;
+use window::NafLookupTable5;
 
 #[allow(unused_imports)]
 use prelude::*;
@@ -108,9 +111,7 @@ impl MultiscalarMul for Straus {
     {
         use clear_on_drop::ClearOnDrop;
 
-        use backend::serial::curve_models::ProjectiveNielsPoint;
         use window::LookupTable;
-        use traits::Identity;
 
         let lookup_tables: Vec<_> = points
             .into_iter()
@@ -159,30 +160,91 @@ impl VartimeMultiscalarMul for Straus {
         I::Item: Borrow<Scalar>,
         J: IntoIterator<Item = Option<EdwardsPoint>>,
     {
-        use backend::serial::curve_models::{CompletedPoint, ProjectiveNielsPoint, ProjectivePoint};
-        use window::NafLookupTable5;
-        use traits::Identity;
+        StrausScratch::default().optional_multiscalar_mul(scalars, points)
+    }
+}
 
-        let nafs: Vec<_> = scalars
-            .into_iter()
-            .map(|c| c.borrow().non_adjacent_form(5))
-            .collect();
+/// Reusable buffers for the variable-time Straus implementation.
+///
+/// Holding on to a `StrausScratch` across calls lets repeated
+/// multiscalar multiplications of similar sizes reuse the same
+/// allocations for the NAF digits and lookup tables.
+#[derive(Default)]
+pub struct StrausScratch {
+    nafs: Vec<[i8; 256]>,
+    lookup_tables: Vec<NafLookupTable5<ProjectiveNielsPoint>>,
+}
 
-        let lookup_tables = match points
-            .into_iter()
-            .map(|P_opt| P_opt.map(|P| NafLookupTable5::<ProjectiveNielsPoint>::from(&P)))
-            .collect::<Option<Vec<_>>>()
-        {
-            Some(x) => x,
-            None => return None,
-        };
+impl StrausScratch {
+    /// Preallocate buffers for up to `size` point-scalar pairs.
+    pub fn with_capacity(size: usize) -> StrausScratch {
+        StrausScratch {
+            nafs: Vec::with_capacity(size),
+            lookup_tables: Vec::with_capacity(size),
+        }
+    }
+
+    /// Variable-time Straus, using `self` for temporary storage.
+    ///
+    /// See the `VartimeMultiscalarMul` implementation for `Straus`.
+    pub fn optional_multiscalar_mul<I, J>(&mut self, scalars: I, points: J) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
+    {
+        self.nafs.clear();
+        self.nafs
+            .extend(scalars.into_iter().map(|c| c.borrow().non_adjacent_form(5)));
+
+        self.lookup_tables.clear();
+        for P_opt in points {
+            match P_opt {
+                Some(P) => self
+                    .lookup_tables
+                    .push(NafLookupTable5::<ProjectiveNielsPoint>::from(&P)),
+                None => return None,
+            }
+        }
+
+        Some(self.sum_with_tables())
+    }
+
+    /// Variable-time Straus for several vectors of scalars with the
+    /// same `points`, using `self` for temporary storage.
+    ///
+    /// The lookup tables depend only on the points, so they are
+    /// built once and shared between all of the scalar vectors.
+    pub fn vartime_multiscalar_mul_batched(
+        &mut self,
+        scalar_vecs: &[&[Scalar]],
+        points: &[EdwardsPoint],
+    ) -> Vec<EdwardsPoint> {
+        self.lookup_tables.clear();
+        self.lookup_tables
+            .extend(points.iter().map(NafLookupTable5::<ProjectiveNielsPoint>::from));
+
+        scalar_vecs
+            .iter()
+            .map(|scalars| {
+                debug_assert_eq!(scalars.len(), points.len());
+                self.nafs.clear();
+                self.nafs
+                    .extend(scalars.iter().map(|c| c.non_adjacent_form(5)));
+                self.sum_with_tables()
+            })
+            .collect()
+    }
 
+    /// Compute the sum of each NAF in `self.nafs` times the point of
+    /// the corresponding table in `self.lookup_tables`.
+    fn sum_with_tables(&self) -> EdwardsPoint {
         let mut r = ProjectivePoint::identity();
 
         for i in (0..256).rev() {
             let mut t: CompletedPoint = r.double();
 
-            for (naf, lookup_table) in nafs.iter().zip(lookup_tables.iter()) {
+            for (naf, lookup_table) in self.nafs.iter().zip(self.lookup_tables.iter()) {
                 if naf[i] > 0 {
                     t = &t.to_extended() + &lookup_table.select(naf[i] as usize);
                 } else if naf[i] < 0 {
@@ -193,6 +255,6 @@ impl VartimeMultiscalarMul for Straus {
             r = t.to_projective();
         }
 
-        Some(r.to_extended())
+        r.to_extended()
     }
 }
@@ -0,0 +1,44 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Constant-time Straus multiscalar multiplication over fixed-size
+//! arrays, which needs no allocator.
+
+#![allow(non_snake_case)]
+
+use clear_on_drop::ClearOnDrop;
+
+use backend::serial::curve_models::ProjectiveNielsPoint;
+use edwards::EdwardsPoint;
+use scalar::Scalar;
+use traits::Identity;
+use window::LookupTable;
+
+/// Compute \\( s_1 P_1 + \cdots + s_N P_N \\) in constant time.
+///
+/// This is the same fixed-window algorithm as the constant-time
+/// `Straus` implementation, but the lookup tables and scalar digits
+/// are stored in arrays on the stack rather than in `Vec`s.  Each
+/// term uses a little over 1KB of stack.
+pub(crate) fn mul<const N: usize>(scalars: &[Scalar; N], points: &[EdwardsPoint; N]) -> EdwardsPoint {
+    let lookup_tables: [LookupTable<ProjectiveNielsPoint>; N] =
+        ::core::array::from_fn(|i| LookupTable::<ProjectiveNielsPoint>::from(&points[i]));
+
+    // Erase the scalar digits from the stack once we are done.
+    let mut scalar_digits_array: [[i8; 64]; N] = ::core::array::from_fn(|i| scalars[i].to_radix_16());
+    let scalar_digits = ClearOnDrop::new(&mut scalar_digits_array[..]);
+
+    let mut Q = EdwardsPoint::identity();
+    for j in (0..64).rev() {
+        Q = Q.mul_by_pow_2(4);
+        for (s_i, lookup_table_i) in scalar_digits.iter().zip(lookup_tables.iter()) {
+            // R_i = s_{i,j} * P_i
+            let R_i = lookup_table_i.select(s_i[j]);
+            // Q = Q + R_i
+            Q = (&Q + &R_i).to_extended();
+        }
+    }
+    Q
+}
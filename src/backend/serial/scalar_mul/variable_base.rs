@@ -4,43 +4,122 @@ use traits::Identity;
 use scalar::Scalar;
 use edwards::EdwardsPoint;
 use backend::serial::curve_models::{ProjectiveNielsPoint, ProjectivePoint};
-use window::LookupTable;
+use window::{LookupTable, LookupTableRadix32, LookupTableRadix64, LookupTableRadix128, LookupTableRadix256};
 
 /// Perform constant-time, variable-base scalar multiplication.
 pub(crate) fn mul(point: &EdwardsPoint, scalar: &Scalar) -> EdwardsPoint {
-    // Construct a lookup table of [P,2P,3P,4P,5P,6P,7P,8P]
-    let lookup_table = LookupTable::<ProjectiveNielsPoint>::from(point);
-    // Setting s = scalar, compute
-    //
-    //    s = s_0 + s_1*16^1 + ... + s_63*16^63,
-    //
-    // with `-8 ≤ s_i < 8` for `0 ≤ i < 63` and `-8 ≤ s_63 ≤ 8`.
-    let scalar_digits = scalar.to_radix_16();
-    // Compute s*P as
-    //
-    //    s*P = P*(s_0 +   s_1*16^1 +   s_2*16^2 + ... +   s_63*16^63)
-    //    s*P =  P*s_0 + P*s_1*16^1 + P*s_2*16^2 + ... + P*s_63*16^63
-    //    s*P = P*s_0 + 16*(P*s_1 + 16*(P*s_2 + 16*( ... + P*s_63)...))
-    //
-    // We sum right-to-left.
-
-    // Unwrap first loop iteration to save computing 16*identity
-    let mut tmp2 = ProjectivePoint::identity();
-    let mut tmp3 = EdwardsPoint::identity();
-    let mut tmp1 = &tmp3 + &lookup_table.select(scalar_digits[63]);
-    // Now tmp1 = s_63*P in P1xP1 coords
-    for i in (0..63).rev() {
-        tmp2 = tmp1.to_projective(); // tmp2 =    (prev) in P2 coords
-        tmp1 = tmp2.double();        // tmp1 =  2*(prev) in P1xP1 coords
-        tmp2 = tmp1.to_projective(); // tmp2 =  2*(prev) in P2 coords
-        tmp1 = tmp2.double();        // tmp1 =  4*(prev) in P1xP1 coords
-        tmp2 = tmp1.to_projective(); // tmp2 =  4*(prev) in P2 coords
-        tmp1 = tmp2.double();        // tmp1 =  8*(prev) in P1xP1 coords
-        tmp2 = tmp1.to_projective(); // tmp2 =  8*(prev) in P2 coords
-        tmp1 = tmp2.double();        // tmp1 = 16*(prev) in P1xP1 coords
-        tmp3 = tmp1.to_extended();   // tmp3 = 16*(prev) in P3 coords
-        tmp1 = &tmp3 + &lookup_table.select(scalar_digits[i]);
-        // Now tmp1 = s_i*P + 16*(prev) in P1xP1 coords
+    VariableBaseTable::new(point).mul(scalar)
+}
+
+/// Perform constant-time, variable-base scalar multiplication using
+/// signed radix-\\(2\^w\\) digits and a table of \\(2\^{w-1}\\) multiples
+/// of `point`.
+///
+/// # Panics
+///
+/// If `w` is not between 4 and 8.
+pub(crate) fn mul_with_window(point: &EdwardsPoint, scalar: &Scalar, w: usize) -> EdwardsPoint {
+    let digits_count = Scalar::to_radix_2w_size_hint(w);
+    let scalar_digits = scalar.to_radix_2w(w);
+    let digits = &scalar_digits[..digits_count];
+
+    match w {
+        4 => {
+            let table = LookupTable::<ProjectiveNielsPoint>::from(point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        5 => {
+            let table = LookupTableRadix32::<ProjectiveNielsPoint>::from(point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        6 => {
+            let table = LookupTableRadix64::<ProjectiveNielsPoint>::from(point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        7 => {
+            let table = LookupTableRadix128::<ProjectiveNielsPoint>::from(point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        8 => {
+            let table = LookupTableRadix256::<ProjectiveNielsPoint>::from(point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        _ => panic!("window width must be in 4..=8"),
+    }
+}
+
+/// Compute \\( \sum\_i s\_i 2\^{wi} P \\) right-to-left, where `select`
+/// returns \\(s\_i P\\) in constant time.
+fn windowed_mul<F>(select: F, digits: &[i8], w: usize) -> EdwardsPoint
+where
+    F: Fn(i8) -> ProjectiveNielsPoint,
+{
+    let last = digits.len() - 1;
+    let mut tmp1 = &EdwardsPoint::identity() + &select(digits[last]);
+    for i in (0..last).rev() {
+        let mut tmp2 = tmp1.to_projective();
+        for _ in 1..w {
+            tmp2 = tmp2.double().to_projective();
+        }
+        // Now tmp2 = 2^(w-1)*(prev), so one more doubling gives 2^w*(prev)
+        let tmp3 = tmp2.double().to_extended();
+        tmp1 = &tmp3 + &select(digits[i]);
     }
     tmp1.to_extended()
 }
+
+/// The lookup table of \\([1]P, \ldots, [8]P\\) used by `mul`, which
+/// can be kept to multiply the same point \\(P\\) by many scalars.
+#[derive(Copy, Clone)]
+pub(crate) struct VariableBaseTable(LookupTable<ProjectiveNielsPoint>);
+
+impl VariableBaseTable {
+    pub(crate) fn new(point: &EdwardsPoint) -> VariableBaseTable {
+        // Construct a lookup table of [P,2P,3P,4P,5P,6P,7P,8P]
+        VariableBaseTable(LookupTable::<ProjectiveNielsPoint>::from(point))
+    }
+
+    /// Perform constant-time scalar multiplication of the table's point.
+    pub(crate) fn mul(&self, scalar: &Scalar) -> EdwardsPoint {
+        // Setting s = scalar, compute
+        //
+        //    s = s_0 + s_1*16^1 + ... + s_63*16^63,
+        //
+        // with `-8 ≤ s_i < 8` for `0 ≤ i < 63` and `-8 ≤ s_63 ≤ 8`.
+        self.mul_radix_16(&scalar.to_radix_16())
+    }
+
+    /// Perform constant-time scalar multiplication of the table's
+    /// point by the scalar with radix-16 digits `scalar_digits`, as
+    /// returned by `Scalar::to_radix_16`.
+    pub(crate) fn mul_radix_16(&self, scalar_digits: &[i8; 64]) -> EdwardsPoint {
+        let lookup_table = &self.0;
+        // Compute s*P as
+        //
+        //    s*P = P*(s_0 +   s_1*16^1 +   s_2*16^2 + ... +   s_63*16^63)
+        //    s*P =  P*s_0 + P*s_1*16^1 + P*s_2*16^2 + ... + P*s_63*16^63
+        //    s*P = P*s_0 + 16*(P*s_1 + 16*(P*s_2 + 16*( ... + P*s_63)...))
+        //
+        // We sum right-to-left.
+
+        // Unwrap first loop iteration to save computing 16*identity
+        let mut tmp2 = ProjectivePoint::identity();
+        let mut tmp3 = EdwardsPoint::identity();
+        let mut tmp1 = &tmp3 + &lookup_table.select(scalar_digits[63]);
+        // Now tmp1 = s_63*P in P1xP1 coords
+        for i in (0..63).rev() {
+            tmp2 = tmp1.to_projective(); // tmp2 =    (prev) in P2 coords
+            tmp1 = tmp2.double();        // tmp1 =  2*(prev) in P1xP1 coords
+            tmp2 = tmp1.to_projective(); // tmp2 =  2*(prev) in P2 coords
+            tmp1 = tmp2.double();        // tmp1 =  4*(prev) in P1xP1 coords
+            tmp2 = tmp1.to_projective(); // tmp2 =  4*(prev) in P2 coords
+            tmp1 = tmp2.double();        // tmp1 =  8*(prev) in P1xP1 coords
+            tmp2 = tmp1.to_projective(); // tmp2 =  8*(prev) in P2 coords
+            tmp1 = tmp2.double();        // tmp1 = 16*(prev) in P1xP1 coords
+            tmp3 = tmp1.to_extended();   // tmp3 = 16*(prev) in P3 coords
+            tmp1 = &tmp3 + &lookup_table.select(scalar_digits[i]);
+            // Now tmp1 = s_i*P + 16*(prev) in P1xP1 coords
+        }
+        tmp1.to_extended()
+    }
+}
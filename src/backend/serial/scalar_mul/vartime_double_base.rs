@@ -14,7 +14,11 @@ use traits::Identity;
 use scalar::Scalar;
 use edwards::EdwardsPoint;
 use backend::serial::curve_models::{ProjectiveNielsPoint, ProjectivePoint};
+#[cfg(not(feature = "stage2_build"))]
+use backend::serial::curve_models::AffineNielsPoint;
 use window::NafLookupTable5;
+#[cfg(not(feature = "stage2_build"))]
+use window::NafLookupTable8;
 
 /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
 pub fn mul(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> EdwardsPoint {
@@ -31,7 +35,56 @@ pub fn mul(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> EdwardsPoint {
     }
 
     let table_A = NafLookupTable5::<ProjectiveNielsPoint>::from(A);
+    // The precomputed table is generated by build.rs, so while
+    // building build.rs itself we compute it on the fly instead.
+    #[cfg(feature = "stage2_build")]
     let table_B = &constants::AFFINE_ODD_MULTIPLES_OF_BASEPOINT;
+    #[cfg(not(feature = "stage2_build"))]
+    let table_B = &NafLookupTable8::<AffineNielsPoint>::from(&constants::ED25519_BASEPOINT_POINT);
+
+    let mut r = ProjectivePoint::identity();
+    loop {
+        let mut t = r.double();
+
+        if a_naf[i] > 0 {
+            t = &t.to_extended() + &table_A.select(a_naf[i] as usize);
+        } else if a_naf[i] < 0 {
+            t = &t.to_extended() - &table_A.select(-a_naf[i] as usize);
+        }
+
+        if b_naf[i] > 0 {
+            t = &t.to_extended() + &table_B.select(b_naf[i] as usize);
+        } else if b_naf[i] < 0 {
+            t = &t.to_extended() - &table_B.select(-b_naf[i] as usize);
+        }
+
+        r = t.to_projective();
+
+        if i == 0 {
+            break;
+        }
+        i -= 1;
+    }
+
+    r.to_extended()
+}
+
+/// Compute \\(aA + bB\\) in variable time, where \\(A\\) and \\(B\\) are arbitrary points.
+pub fn mul_arbitrary(a: &Scalar, A: &EdwardsPoint, b: &Scalar, B: &EdwardsPoint) -> EdwardsPoint {
+    let a_naf = a.non_adjacent_form(5);
+    let b_naf = b.non_adjacent_form(5);
+
+    // Find starting index
+    let mut i: usize = 255;
+    for j in (0..256).rev() {
+        i = j;
+        if a_naf[i] != 0 || b_naf[i] != 0 {
+            break;
+        }
+    }
+
+    let table_A = NafLookupTable5::<ProjectiveNielsPoint>::from(A);
+    let table_B = NafLookupTable5::<ProjectiveNielsPoint>::from(B);
 
     let mut r = ProjectivePoint::identity();
     loop {
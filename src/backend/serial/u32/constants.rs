@@ -51,6 +51,17 @@ pub(crate) const APLUS2_OVER_FOUR: FieldElement2625 = FieldElement2625([
     121666, 0, 0, 0, 0, 0, 0, 0, 0, 0
 ]);
 
+/// The Montgomery curve parameter `A = 486662`.
+pub(crate) const MONTGOMERY_A: FieldElement2625 = FieldElement2625([
+    486662, 0, 0, 0, 0, 0, 0, 0, 0, 0
+]);
+
+/// `-A (mod p)`, the negation of the Montgomery curve parameter `A`.
+pub(crate) const MONTGOMERY_A_NEG: FieldElement2625 = FieldElement2625([
+    66622183, 33554431, 67108863, 33554431, 67108863,
+    33554431, 67108863, 33554431, 67108863, 33554431,
+]);
+
 /// `L` is the order of base point, i.e. 2^252 +
 /// 27742317777372353535851937790883648493
 pub(crate) const L: Scalar29 = Scalar29([ 0x1cf5d3ed, 0x009318d2, 0x1de73596, 0x1df3bd45,
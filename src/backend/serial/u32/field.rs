@@ -24,6 +24,9 @@ use core::ops::{Sub, SubAssign};
 use subtle::Choice;
 use subtle::ConditionallySelectable;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// A `FieldElement2625` represents an element of the field
 /// \\( \mathbb Z / (2\^{255} - 19)\\).
 ///
@@ -55,6 +58,13 @@ impl Debug for FieldElement2625 {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl Zeroize for FieldElement2625 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 impl<'b> AddAssign<&'b FieldElement2625> for FieldElement2625 {
     fn add_assign(&mut self, _rhs: &'b FieldElement2625) {
         for i in 0..10 {
@@ -286,17 +296,17 @@ impl FieldElement2625 {
     }
 
     /// Construct zero.
-    pub fn zero() -> FieldElement2625 {
+    pub const fn zero() -> FieldElement2625 {
         FieldElement2625([ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ])
     }
 
     /// Construct one.
-    pub fn one() -> FieldElement2625 {
+    pub const fn one() -> FieldElement2625 {
         FieldElement2625([ 1, 0, 0, 0, 0, 0, 0, 0, 0, 0 ])
     }
 
     /// Construct -1.
-    pub fn minus_one() -> FieldElement2625 {
+    pub const fn minus_one() -> FieldElement2625 {
         FieldElement2625([
             0x3ffffec, 0x1ffffff, 0x3ffffff, 0x1ffffff, 0x3ffffff,
             0x1ffffff, 0x3ffffff, 0x1ffffff, 0x3ffffff, 0x1ffffff,
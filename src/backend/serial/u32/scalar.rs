@@ -13,6 +13,8 @@
 use core::fmt::Debug;
 use core::ops::{Index, IndexMut};
 
+use subtle::{Choice, ConditionallySelectable};
+
 use constants;
 
 /// The `Scalar29` struct represents an element in ℤ/lℤ as 9 29-bit limbs
@@ -25,6 +27,16 @@ impl Debug for Scalar29 {
     }
 }
 
+impl ConditionallySelectable for Scalar29 {
+    fn conditional_select(a: &Scalar29, b: &Scalar29, choice: Choice) -> Scalar29 {
+        let mut output = [0u32; 9];
+        for (i, limb) in output.iter_mut().enumerate() {
+            *limb = u32::conditional_select(&a.0[i], &b.0[i], choice);
+        }
+        Scalar29(output)
+    }
+}
+
 impl Index<usize> for Scalar29 {
     type Output = u32;
     fn index(&self, _index: usize) -> &u32 {
@@ -341,7 +353,6 @@ impl Scalar29 {
 
     /// Compute `a^2` (mod l).
     #[inline(never)]
-    #[allow(dead_code)] // XXX we don't expose square() via the Scalar API
     pub fn square(&self) -> Scalar29 {
         let aa = Scalar29::montgomery_reduce(&Scalar29::square_internal(self));
         Scalar29::montgomery_reduce(&Scalar29::mul_internal(&aa, &constants::RR))
@@ -36,6 +36,12 @@ pub(crate) const SQRT_M1: FieldElement51 = FieldElement51([1718705420411056, 234
 /// `APLUS2_OVER_FOUR` is (A+2)/4. (This is used internally within the Montgomery ladder.)
 pub(crate) const APLUS2_OVER_FOUR: FieldElement51 = FieldElement51([121666, 0, 0, 0, 0]);
 
+/// The Montgomery curve parameter `A = 486662`.
+pub(crate) const MONTGOMERY_A: FieldElement51 = FieldElement51([486662, 0, 0, 0, 0]);
+
+/// `-A (mod p)`, the negation of the Montgomery curve parameter `A`.
+pub(crate) const MONTGOMERY_A_NEG: FieldElement51 = FieldElement51([2251799813198567, 2251799813685247, 2251799813685247, 2251799813685247, 2251799813685247]);
+
 /// `L` is the order of base point, i.e. 2^252 + 27742317777372353535851937790883648493
 pub(crate) const L: Scalar52 = Scalar52([ 0x0002631a5cf5d3ed, 0x000dea2f79cd6581, 0x000000000014def9, 0x0000000000000000, 0x0000100000000000 ]);
 
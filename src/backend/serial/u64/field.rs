@@ -20,6 +20,9 @@ use core::ops::{Sub, SubAssign};
 use subtle::Choice;
 use subtle::ConditionallySelectable;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// A `FieldElement51` represents an element of the field
 /// \\( \mathbb Z / (2\^{255} - 19)\\).
 ///
@@ -44,6 +47,13 @@ impl Debug for FieldElement51 {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl Zeroize for FieldElement51 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 impl<'b> AddAssign<&'b FieldElement51> for FieldElement51 {
     fn add_assign(&mut self, _rhs: &'b FieldElement51) {
         for i in 0..5 {
@@ -256,17 +266,17 @@ impl FieldElement51 {
     }
 
     /// Construct zero.
-    pub fn zero() -> FieldElement51 {
+    pub const fn zero() -> FieldElement51 {
         FieldElement51([ 0, 0, 0, 0, 0 ])
     }
 
     /// Construct one.
-    pub fn one() -> FieldElement51 {
+    pub const fn one() -> FieldElement51 {
         FieldElement51([ 1, 0, 0, 0, 0 ])
     }
 
     /// Construct -1.
-    pub fn minus_one() -> FieldElement51 {
+    pub const fn minus_one() -> FieldElement51 {
         FieldElement51([2251799813685228, 2251799813685247, 2251799813685247, 2251799813685247, 2251799813685247])
     }
 
@@ -14,6 +14,8 @@
 use core::fmt::Debug;
 use core::ops::{Index, IndexMut};
 
+use subtle::{Choice, ConditionallySelectable};
+
 use constants;
 
 /// The `Scalar52` struct represents an element in
@@ -27,6 +29,16 @@ impl Debug for Scalar52 {
     }
 }
 
+impl ConditionallySelectable for Scalar52 {
+    fn conditional_select(a: &Scalar52, b: &Scalar52, choice: Choice) -> Scalar52 {
+        let mut output = [0u64; 5];
+        for (i, limb) in output.iter_mut().enumerate() {
+            *limb = u64::conditional_select(&a.0[i], &b.0[i], choice);
+        }
+        Scalar52(output)
+    }
+}
+
 impl Index<usize> for Scalar52 {
     type Output = u64;
     fn index(&self, _index: usize) -> &u64 {
@@ -270,7 +282,6 @@ impl Scalar52 {
 
     /// Compute `a^2` (mod l)
     #[inline(never)]
-    #[allow(dead_code)] // XXX we don't expose square() via the Scalar API
     pub fn square(&self) -> Scalar52 {
         let aa = Scalar52::montgomery_reduce(&Scalar52::square_internal(self));
         Scalar52::montgomery_reduce(&Scalar52::mul_internal(&aa, &constants::RR))
@@ -79,25 +79,89 @@ impl VartimeMultiscalarMul for Straus {
         I::Item: Borrow<Scalar>,
         J: IntoIterator<Item = Option<EdwardsPoint>>,
     {
-        let nafs: Vec<_> = scalars
-            .into_iter()
-            .map(|c| c.borrow().non_adjacent_form(5))
-            .collect();
-        let lookup_tables: Vec<_> = match points
-            .into_iter()
-            .map(|P_opt| P_opt.map(|P| NafLookupTable5::<CachedPoint>::from(&P)))
-            .collect::<Option<Vec<_>>>()
-        {
-            Some(x) => x,
-            None => return None,
-        };
+        StrausScratch::default().optional_multiscalar_mul(scalars, points)
+    }
+}
+
+/// Reusable buffers for the variable-time Straus implementation.
+///
+/// Holding on to a `StrausScratch` across calls lets repeated
+/// multiscalar multiplications of similar sizes reuse the same
+/// allocations for the NAF digits and lookup tables.
+#[derive(Default)]
+pub struct StrausScratch {
+    nafs: Vec<[i8; 256]>,
+    lookup_tables: Vec<NafLookupTable5<CachedPoint>>,
+}
+
+impl StrausScratch {
+    /// Preallocate buffers for up to `size` point-scalar pairs.
+    pub fn with_capacity(size: usize) -> StrausScratch {
+        StrausScratch {
+            nafs: Vec::with_capacity(size),
+            lookup_tables: Vec::with_capacity(size),
+        }
+    }
+
+    /// Variable-time Straus, using `self` for temporary storage.
+    pub fn optional_multiscalar_mul<I, J>(&mut self, scalars: I, points: J) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
+    {
+        self.nafs.clear();
+        self.nafs
+            .extend(scalars.into_iter().map(|c| c.borrow().non_adjacent_form(5)));
+
+        self.lookup_tables.clear();
+        for P_opt in points {
+            match P_opt {
+                Some(P) => self
+                    .lookup_tables
+                    .push(NafLookupTable5::<CachedPoint>::from(&P)),
+                None => return None,
+            }
+        }
+
+        Some(self.sum_with_tables())
+    }
 
+    /// Variable-time Straus for several vectors of scalars with the
+    /// same `points`, using `self` for temporary storage.
+    ///
+    /// The lookup tables depend only on the points, so they are
+    /// built once and shared between all of the scalar vectors.
+    pub fn vartime_multiscalar_mul_batched(
+        &mut self,
+        scalar_vecs: &[&[Scalar]],
+        points: &[EdwardsPoint],
+    ) -> Vec<EdwardsPoint> {
+        self.lookup_tables.clear();
+        self.lookup_tables
+            .extend(points.iter().map(NafLookupTable5::<CachedPoint>::from));
+
+        scalar_vecs
+            .iter()
+            .map(|scalars| {
+                debug_assert_eq!(scalars.len(), points.len());
+                self.nafs.clear();
+                self.nafs
+                    .extend(scalars.iter().map(|c| c.non_adjacent_form(5)));
+                self.sum_with_tables()
+            })
+            .collect()
+    }
+
+    /// Compute the sum of each NAF in `self.nafs` times the point of
+    /// the corresponding table in `self.lookup_tables`.
+    fn sum_with_tables(&self) -> EdwardsPoint {
         let mut Q = ExtendedPoint::identity();
 
         for i in (0..256).rev() {
             Q = Q.double();
 
-            for (naf, lookup_table) in nafs.iter().zip(lookup_tables.iter()) {
+            for (naf, lookup_table) in self.nafs.iter().zip(self.lookup_tables.iter()) {
                 if naf[i] > 0 {
                     Q = &Q + &lookup_table.select(naf[i] as usize);
                 } else if naf[i] < 0 {
@@ -106,6 +170,6 @@ impl VartimeMultiscalarMul for Straus {
             }
         }
 
-        Some(Q.into())
+        Q.into()
     }
 }
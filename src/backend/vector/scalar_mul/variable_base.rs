@@ -4,29 +4,114 @@ use backend::vector::{CachedPoint, ExtendedPoint};
 use edwards::EdwardsPoint;
 use scalar::Scalar;
 use traits::Identity;
-use window::LookupTable;
+use window::{LookupTable, LookupTableRadix32, LookupTableRadix64, LookupTableRadix128, LookupTableRadix256};
 
 /// Perform constant-time, variable-base scalar multiplication.
 pub fn mul(point: &EdwardsPoint, scalar: &Scalar) -> EdwardsPoint {
-    // Construct a lookup table of [P,2P,3P,4P,5P,6P,7P,8P]
-    let lookup_table = LookupTable::<CachedPoint>::from(point);
-    // Setting s = scalar, compute
-    //
-    //    s = s_0 + s_1*16^1 + ... + s_63*16^63,
-    //
-    // with `-8 ≤ s_i < 8` for `0 ≤ i < 63` and `-8 ≤ s_63 ≤ 8`.
-    let scalar_digits = scalar.to_radix_16();
-    // Compute s*P as
-    //
-    //    s*P = P*(s_0 +   s_1*16^1 +   s_2*16^2 + ... +   s_63*16^63)
-    //    s*P =  P*s_0 + P*s_1*16^1 + P*s_2*16^2 + ... + P*s_63*16^63
-    //    s*P = P*s_0 + 16*(P*s_1 + 16*(P*s_2 + 16*( ... + P*s_63)...))
-    //
-    // We sum right-to-left.
+    VariableBaseTable::new(point).mul(scalar)
+}
+
+/// Build a lookup table of \\([1]P, \ldots, [N]P\\) in `CachedPoint`s.
+macro_rules! cached_lookup_table {
+    ($name:ident, $size:expr, $point:expr) => {{
+        let P = ExtendedPoint::from(*$point);
+        let mut points = [CachedPoint::from(P); $size];
+        for i in 0..($size - 1) {
+            points[i + 1] = (&P + &points[i]).into();
+        }
+        $name(points)
+    }};
+}
+
+/// Perform constant-time, variable-base scalar multiplication using
+/// signed radix-\\(2\^w\\) digits and a table of \\(2\^{w-1}\\) multiples
+/// of `point`.
+///
+/// # Panics
+///
+/// If `w` is not between 4 and 8.
+pub fn mul_with_window(point: &EdwardsPoint, scalar: &Scalar, w: usize) -> EdwardsPoint {
+    let digits_count = Scalar::to_radix_2w_size_hint(w);
+    let scalar_digits = scalar.to_radix_2w(w);
+    let digits = &scalar_digits[..digits_count];
+
+    match w {
+        4 => {
+            let table = cached_lookup_table!(LookupTable, 8, point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        5 => {
+            let table = cached_lookup_table!(LookupTableRadix32, 16, point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        6 => {
+            let table = cached_lookup_table!(LookupTableRadix64, 32, point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        7 => {
+            let table = cached_lookup_table!(LookupTableRadix128, 64, point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        8 => {
+            let table = cached_lookup_table!(LookupTableRadix256, 128, point);
+            windowed_mul(|x| table.select(x), digits, w)
+        }
+        _ => panic!("window width must be in 4..=8"),
+    }
+}
+
+/// Compute \\( \sum\_i s\_i 2\^{wi} P \\) right-to-left, where `select`
+/// returns \\(s\_i P\\) in constant time.
+fn windowed_mul<F>(select: F, digits: &[i8], w: usize) -> EdwardsPoint
+where
+    F: Fn(i8) -> CachedPoint,
+{
     let mut Q = ExtendedPoint::identity();
-    for i in (0..64).rev() {
-        Q = Q.mul_by_pow_2(4);
-        Q = &Q + &lookup_table.select(scalar_digits[i]);
+    for &digit in digits.iter().rev() {
+        Q = Q.mul_by_pow_2(w as u32);
+        Q = &Q + &select(digit);
     }
     Q.into()
 }
+
+/// The lookup table of \\([1]P, \ldots, [8]P\\) used by `mul`, which
+/// can be kept to multiply the same point \\(P\\) by many scalars.
+#[derive(Copy, Clone)]
+pub struct VariableBaseTable(LookupTable<CachedPoint>);
+
+impl VariableBaseTable {
+    pub fn new(point: &EdwardsPoint) -> VariableBaseTable {
+        // Construct a lookup table of [P,2P,3P,4P,5P,6P,7P,8P]
+        VariableBaseTable(LookupTable::<CachedPoint>::from(point))
+    }
+
+    /// Perform constant-time scalar multiplication of the table's point.
+    pub fn mul(&self, scalar: &Scalar) -> EdwardsPoint {
+        // Setting s = scalar, compute
+        //
+        //    s = s_0 + s_1*16^1 + ... + s_63*16^63,
+        //
+        // with `-8 ≤ s_i < 8` for `0 ≤ i < 63` and `-8 ≤ s_63 ≤ 8`.
+        self.mul_radix_16(&scalar.to_radix_16())
+    }
+
+    /// Perform constant-time scalar multiplication of the table's
+    /// point by the scalar with radix-16 digits `scalar_digits`, as
+    /// returned by `Scalar::to_radix_16`.
+    pub fn mul_radix_16(&self, scalar_digits: &[i8; 64]) -> EdwardsPoint {
+        let lookup_table = &self.0;
+        // Compute s*P as
+        //
+        //    s*P = P*(s_0 +   s_1*16^1 +   s_2*16^2 + ... +   s_63*16^63)
+        //    s*P =  P*s_0 + P*s_1*16^1 + P*s_2*16^2 + ... + P*s_63*16^63
+        //    s*P = P*s_0 + 16*(P*s_1 + 16*(P*s_2 + 16*( ... + P*s_63)...))
+        //
+        // We sum right-to-left.
+        let mut Q = ExtendedPoint::identity();
+        for i in (0..64).rev() {
+            Q = Q.mul_by_pow_2(4);
+            Q = &Q + &lookup_table.select(scalar_digits[i]);
+        }
+        Q.into()
+    }
+}
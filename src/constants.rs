@@ -173,6 +173,17 @@ mod test {
         assert_eq!(d2, constants::EDWARDS_D2);
     }
 
+    /// Test that MONTGOMERY_A_NEG = -MONTGOMERY_A, and that
+    /// APLUS2_OVER_FOUR = (MONTGOMERY_A + 2)/4
+    #[test]
+    fn test_montgomery_a() {
+        let one = FieldElement::one();
+        let two = &one + &one;
+        let four = &two + &two;
+        assert_eq!(-&constants::MONTGOMERY_A, constants::MONTGOMERY_A_NEG);
+        assert_eq!(&constants::APLUS2_OVER_FOUR * &four, &constants::MONTGOMERY_A + &two);
+    }
+
     #[test]
     fn test_sqrt_ad_minus_one() {
         let a = FieldElement::minus_one();
@@ -92,22 +92,35 @@
 // affine and projective cakes and eat both of them too.
 #![allow(non_snake_case)]
 
+use core::array::TryFromSliceError;
 use core::borrow::Borrow;
+#[cfg(feature = "alloc")]
+use core::cmp;
+use core::convert::TryFrom;
 use core::fmt::Debug;
+#[cfg(feature = "alloc")]
+use core::iter;
 use core::iter::Iterator;
 use core::iter::Sum;
 use core::ops::{Add, Neg, Sub};
 use core::ops::{AddAssign, SubAssign};
 use core::ops::{Mul, MulAssign};
 
+use rand_core::{CryptoRng, RngCore};
+
 use subtle::Choice;
 use subtle::ConditionallyNegatable;
 use subtle::ConditionallySelectable;
 use subtle::ConstantTimeEq;
+use subtle::CtOption;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 use constants;
 
 use field::FieldElement;
+use hex;
 use scalar::Scalar;
 
 use montgomery::MontgomeryPoint;
@@ -118,6 +131,9 @@ use backend::serial::curve_models::ProjectiveNielsPoint;
 use backend::serial::curve_models::ProjectivePoint;
 
 use window::LookupTable;
+use window::NafLookupTable5;
+use window::NafLookupTable8;
+use window::{LookupTableRadix128, LookupTableRadix256, LookupTableRadix32, LookupTableRadix64};
 
 #[allow(unused_imports)]
 use prelude::*;
@@ -150,7 +166,7 @@ use backend::vector::scalar_mul;
 ///
 /// The first 255 bits of a `CompressedEdwardsY` represent the
 /// \\(y\\)-coordinate.  The high bit of the 32nd byte gives the sign of \\(x\\).
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct CompressedEdwardsY(pub [u8; 32]);
 
 impl ConstantTimeEq for CompressedEdwardsY {
@@ -159,12 +175,72 @@ impl ConstantTimeEq for CompressedEdwardsY {
     }
 }
 
+impl ConditionallySelectable for CompressedEdwardsY {
+    fn conditional_select(a: &CompressedEdwardsY, b: &CompressedEdwardsY, choice: Choice) -> CompressedEdwardsY {
+        let mut bytes = [0u8; 32];
+        for (byte, (a_byte, b_byte)) in bytes.iter_mut().zip(a.0.iter().zip(b.0.iter())) {
+            *byte = u8::conditional_select(a_byte, b_byte, choice);
+        }
+        CompressedEdwardsY(bytes)
+    }
+}
+
 impl Debug for CompressedEdwardsY {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "CompressedEdwardsY: {:?}", self.as_bytes())
     }
 }
 
+/// The reason a `CompressedEdwardsY` failed to decompress, as returned
+/// by `CompressedEdwardsY::decompress_result` and
+/// `CompressedEdwardsY::decompress_strict_result`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DecompressionError {
+    /// The bytes are not the \\(y\\)-coordinate of a curve point:
+    /// \\( (y\^2 - 1)/(dy\^2 + 1) \\) has no square root.
+    NotOnCurve,
+    /// The bytes decode to a curve point, but are not the canonical
+    /// encoding of that point (see `CompressedEdwardsY::is_canonical`).
+    NonCanonical,
+    /// The bytes decode to a curve point which is not in the
+    /// prime-order subgroup.
+    HasTorsion,
+}
+
+impl ::core::fmt::Display for DecompressionError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match *self {
+            DecompressionError::NotOnCurve => write!(f, "encoding is not the y-coordinate of a curve point"),
+            DecompressionError::NonCanonical => write!(f, "encoding is not canonical"),
+            DecompressionError::HasTorsion => write!(f, "point has a nonzero torsion component"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for DecompressionError {}
+
+/// The error returned by `CompressedEdwardsY::from_hex`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FromHexError {
+    /// The string was not exactly 64 characters long.
+    InvalidLength,
+    /// The string contained a character which is not a hex digit.
+    InvalidDigit,
+}
+
+impl ::core::fmt::Display for FromHexError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match *self {
+            FromHexError::InvalidLength => write!(f, "hex encoding must be 64 characters"),
+            FromHexError::InvalidDigit => write!(f, "hex encoding contains an invalid digit"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for FromHexError {}
+
 impl CompressedEdwardsY {
     /// View this `CompressedEdwardsY` as an array of bytes.
     pub fn as_bytes(&self) -> &[u8; 32] {
@@ -176,19 +252,233 @@ impl CompressedEdwardsY {
         self.0
     }
 
+    /// Get the compressed encoding of the negation of the point this
+    /// encodes, without decompressing it.
+    ///
+    /// Negation only changes the sign of \\(x\\), so this flips the
+    /// sign bit, except when \\(x = 0\\) (that is, \\(y = \pm 1\\)),
+    /// where the point is its own negation and the encoding is
+    /// returned unchanged.  Thus `P.compress().negate()` equals
+    /// `(-P).compress()` for every `EdwardsPoint` `P`.
+    ///
+    /// This runs in constant time, and does not check that the bytes
+    /// encode a curve point.
+    pub fn negate(&self) -> CompressedEdwardsY {
+        let mut y_bytes = self.to_bytes();
+        y_bytes[31] &= 0x7f;
+        let x_is_zero = y_bytes.ct_eq(&FieldElement::one().to_bytes())
+            | y_bytes.ct_eq(&FieldElement::minus_one().to_bytes());
+
+        let mut negated = self.to_bytes();
+        negated[31] ^= (!x_is_zero).unwrap_u8() << 7;
+        CompressedEdwardsY(negated)
+    }
+
+    /// Encode a point given its affine \\(y\\)-coordinate and the sign
+    /// of its \\(x\\)-coordinate, as returned by `FieldElement::is_negative`.
+    ///
+    /// This writes the canonical bytes of \\(y\\) and sets the top bit
+    /// if `x_is_negative` is set, in constant time.  It does not check
+    /// that \\(y\\) is the coordinate of a curve point, or that the
+    /// sign bit is clear when \\(x = 0\\).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::CompressedEdwardsY;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let (x, y) = B.to_affine();
+    ///
+    /// assert_eq!(CompressedEdwardsY::from_y_and_sign(&y, x.is_negative()), B.compress());
+    /// ```
+    pub fn from_y_and_sign(y: &FieldElement, x_is_negative: Choice) -> CompressedEdwardsY {
+        let mut s = y.to_bytes();
+        s[31] ^= x_is_negative.unwrap_u8() << 7;
+        CompressedEdwardsY(s)
+    }
+
+    /// Parse a `CompressedEdwardsY` from 64 hex digits, in either case,
+    /// giving the bytes of the encoding in order.
+    ///
+    /// This only decodes the bytes; it does not check that they are
+    /// the encoding of a curve point, which is done by `decompress`.
+    ///
+    /// Since this is a `const fn`, it can be used to declare `const`
+    /// points in source:
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # use curve25519_dalek::constants;
+    /// # use curve25519_dalek::edwards::{CompressedEdwardsY, FromHexError};
+    /// # fn main() {
+    /// const B: CompressedEdwardsY = match CompressedEdwardsY::from_hex(
+    ///     "5866666666666666666666666666666666666666666666666666666666666666",
+    /// ) {
+    ///     Ok(point) => point,
+    ///     Err(_) => panic!("invalid hex"),
+    /// };
+    /// assert_eq!(B, constants::ED25519_BASEPOINT_COMPRESSED);
+    ///
+    /// assert_eq!(CompressedEdwardsY::from_hex("58"), Err(FromHexError::InvalidLength));
+    /// # }
+    /// ```
+    pub const fn from_hex(s: &str) -> Result<CompressedEdwardsY, FromHexError> {
+        if s.len() != 64 {
+            return Err(FromHexError::InvalidLength);
+        }
+        match hex::decode(s) {
+            Some(bytes) => Ok(CompressedEdwardsY(bytes)),
+            None => Err(FromHexError::InvalidDigit),
+        }
+    }
+
     /// Attempt to decompress to an `EdwardsPoint`.
     ///
     /// Returns `None` if the input is not the \\(y\\)-coordinate of a
     /// curve point.
+    ///
+    /// Almost all of the cost of decompression is the square root
+    /// needed to recover \\(x\\) from \\(y\\) (see
+    /// `FieldElement::sqrt_ratio_i`); the validity check comes
+    /// out of that computation essentially for free, so skipping it
+    /// would not make decompression meaningfully faster.  To load
+    /// points quickly, store them with
+    /// `EdwardsPoint::to_bytes_uncompressed` and load them with
+    /// `EdwardsPoint::from_bytes_uncompressed`, which needs no square
+    /// root.
     pub fn decompress(&self) -> Option<EdwardsPoint> {
+        self.decompress_result().ok()
+    }
+
+    /// Attempt to decompress to an `EdwardsPoint`, reporting why
+    /// decompression failed.
+    ///
+    /// This accepts exactly the same inputs as `decompress`, and
+    /// returns `DecompressionError::NotOnCurve` where it would return
+    /// `None`.
+    pub fn decompress_result(&self) -> Result<EdwardsPoint, DecompressionError> {
+        let (is_valid_y_coord, point) = self.ct_decompress();
+
+        if is_valid_y_coord.unwrap_u8() != 1u8 {
+            return Err(DecompressionError::NotOnCurve);
+        }
+
+        Ok(point)
+    }
+
+    /// Attempt to decompress to an `EdwardsPoint`, also returning
+    /// the Montgomery \\(u\\)-coordinate of the point.
+    ///
+    /// This accepts exactly the same inputs as `decompress`, and the
+    /// \\(u\\)-coordinate is the same as `to_montgomery` would
+    /// compute, but the square root and the inversion of
+    /// \\(1-y\\) are merged into one exponentiation, so this is
+    /// cheaper than calling `decompress` and then `to_montgomery`.
+    ///
+    /// ```
+    /// # use curve25519_dalek::constants;
+    /// let B = constants::ED25519_BASEPOINT_COMPRESSED;
+    /// let (P, u) = B.decompress_with_montgomery().unwrap();
+    ///
+    /// assert_eq!(P, constants::ED25519_BASEPOINT_POINT);
+    /// assert_eq!(u, constants::X25519_BASEPOINT);
+    /// ```
+    pub fn decompress_with_montgomery(&self) -> Option<(EdwardsPoint, MontgomeryPoint)> {
         let Y = FieldElement::from_bytes(self.as_bytes());
         let Z = FieldElement::one();
         let YY = Y.square();
         let u = &YY - &Z;                            // u =  y²-1
         let v = &(&YY * &constants::EDWARDS_D) + &Z; // v = dy²+1
-        let (is_valid_y_coord, mut X) = FieldElement::sqrt_ratio_i(&u, &v);
+        let w = &Z - &Y;                             // w =  1-y
 
-        if is_valid_y_coord.unwrap_u8() != 1u8 { return None; }
+        // Set I = 1/sqrt(u*v*w²).  Then x = u*w*I is a square root of
+        // u/v, and x*v*I = u*v*w*I² = 1/w, whichever root I is.
+        //
+        // If u = 0 then y = ±1, so x = 0, and since I = 0 we get 1/w = 0
+        // as well, matching `to_montgomery` on these points.  Otherwise
+        // w is nonzero, so u*v*w² is a nonzero square exactly when
+        // u/v is.
+        let (is_nonzero_square, I) = (&(&u * &v) * &w.square()).invsqrt();
+        let is_valid_y_coord = is_nonzero_square | u.is_zero();
+
+        let mut X = &(&u * &w) * &I;
+        let w_inv = &(&X * &v) * &I;
+        let U = &(&Z + &Y) * &w_inv;
+
+        // Flip the sign of X if it's not correct
+        let compressed_sign_bit = Choice::from(self.as_bytes()[31] >> 7);
+        let    current_sign_bit = X.is_negative();
+
+        X.conditional_negate(current_sign_bit ^ compressed_sign_bit);
+
+        if is_valid_y_coord.unwrap_u8() != 1u8 {
+            return None;
+        }
+
+        let point = EdwardsPoint{ X, Y, Z, T: &X * &Y };
+        Some((point, MontgomeryPoint(U.to_bytes())))
+    }
+
+    /// Decompress `a` if `choice` is unset, or `b` if `choice` is set,
+    /// in constant time.
+    ///
+    /// This selects between the encodings with
+    /// `ConditionallySelectable`, then decompresses the selected one
+    /// once, as `decompress` would.  The result is returned as a
+    /// `CtOption`, which is none if the selected encoding is not the
+    /// \\(y\\)-coordinate of a curve point.
+    ///
+    /// # Constant-time guarantees
+    ///
+    /// The sequence of operations, and so the running time, is
+    /// independent of `choice`, of the bytes of `a` and `b`, and of
+    /// whether decompression succeeds.  Callers which must not reveal
+    /// `choice` should keep working with the `CtOption` (for instance
+    /// with `CtOption::unwrap_or` or `ConditionallySelectable`),
+    /// since branching on `is_some()` reveals whether the selected
+    /// encoding was valid, which may depend on `choice` when only one
+    /// of `a` and `b` is valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # extern crate subtle;
+    /// # use curve25519_dalek::constants;
+    /// # use curve25519_dalek::edwards::CompressedEdwardsY;
+    /// # use subtle::Choice;
+    /// # fn main() {
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let a = B.compress();
+    /// let b = (B + B).compress();
+    ///
+    /// let P = CompressedEdwardsY::conditional_decompress(&a, &b, Choice::from(1));
+    /// assert_eq!(P.unwrap(), B + B);
+    /// # }
+    /// ```
+    pub fn conditional_decompress(
+        a: &CompressedEdwardsY,
+        b: &CompressedEdwardsY,
+        choice: Choice,
+    ) -> CtOption<EdwardsPoint> {
+        let selected = CompressedEdwardsY::conditional_select(a, b, choice);
+        let (is_valid_y_coord, point) = selected.ct_decompress();
+        CtOption::new(point, is_valid_y_coord)
+    }
+
+    /// Decompress in constant time, returning a `Choice` which is
+    /// set if the input is the \\(y\\)-coordinate of a curve point.
+    ///
+    /// The returned point is only meaningful if the `Choice` is set.
+    pub(crate) fn ct_decompress(&self) -> (Choice, EdwardsPoint) {
+        let Y = FieldElement::from_bytes(self.as_bytes());
+        let Z = FieldElement::one();
+        let YY = Y.square();
+        let u = &YY - &Z;                            // u =  y²-1
+        let v = &(&YY * &constants::EDWARDS_D) + &Z; // v = dy²+1
+        let (is_valid_y_coord, mut X) = FieldElement::sqrt_ratio_i(&u, &v);
 
         // Flip the sign of X if it's not correct
         let compressed_sign_bit = Choice::from(self.as_bytes()[31] >> 7);
@@ -196,10 +486,153 @@ impl CompressedEdwardsY {
 
         X.conditional_negate(current_sign_bit ^ compressed_sign_bit);
 
-        Some(EdwardsPoint{ X: X, Y: Y, Z: Z, T: &X * &Y })
+        (is_valid_y_coord, EdwardsPoint{ X: X, Y: Y, Z: Z, T: &X * &Y })
+    }
+
+    /// Attempt to decompress to an `EdwardsPoint`, accepting only
+    /// canonical encodings of points in the prime-order subgroup.
+    ///
+    /// Returns `None` if the input is not the \\(y\\)-coordinate of a
+    /// curve point, if it is not the canonical encoding of that point
+    /// (see `is_canonical`), or if the point has a nonzero torsion
+    /// component (see `EdwardsPoint::is_torsion_free`).
+    ///
+    /// This is safe for protocols that must reject small-order and
+    /// non-canonical points, with one caveat: the identity is in the
+    /// prime-order subgroup, so it is accepted.  Protocols which must
+    /// also reject the identity should check `is_identity` on the
+    /// result.
+    pub fn decompress_strict(&self) -> Option<EdwardsPoint> {
+        self.decompress_strict_result().ok()
+    }
+
+    /// Attempt to decompress to an `EdwardsPoint` with the same checks
+    /// as `decompress_strict`, reporting which check failed.
+    ///
+    /// The checks are made in the order listed in
+    /// `DecompressionError`, and the first failure is returned.
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # use curve25519_dalek::constants;
+    /// # use curve25519_dalek::edwards::{CompressedEdwardsY, DecompressionError};
+    /// # fn main() {
+    /// let B = constants::ED25519_BASEPOINT_COMPRESSED;
+    /// assert!(B.decompress_strict_result().is_ok());
+    ///
+    /// // y = 2 is not the y-coordinate of any curve point
+    /// let mut bytes = [0u8; 32];
+    /// bytes[0] = 2;
+    /// assert_eq!(CompressedEdwardsY(bytes).decompress_strict_result(),
+    ///            Err(DecompressionError::NotOnCurve));
+    ///
+    /// // The order-2 point (0, -1) decompresses, but is not torsion-free
+    /// let T = constants::EIGHT_TORSION[4].compress();
+    /// assert_eq!(T.decompress_strict_result(), Err(DecompressionError::HasTorsion));
+    /// # }
+    /// ```
+    pub fn decompress_strict_result(&self) -> Result<EdwardsPoint, DecompressionError> {
+        let P = self.decompress_result()?;
+
+        if !self.is_canonical() {
+            return Err(DecompressionError::NonCanonical);
+        }
+        if !P.is_torsion_free() {
+            return Err(DecompressionError::HasTorsion);
+        }
+
+        Ok(P)
+    }
+
+    /// Decompress a batch of points.
+    ///
+    /// The result has one entry per input, in the same order, which is
+    /// exactly what `decompress` returns for that input.
+    ///
+    /// This calls `decompress` on each input: there is no batched
+    /// serial algorithm, since decompression is dominated by a square
+    /// root (see `FieldElement::sqrt_ratio_i`).  With the `parallel`
+    /// feature, large batches are split into chunks which are
+    /// decompressed on the rayon thread pool.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # use curve25519_dalek::constants;
+    /// # use curve25519_dalek::edwards::CompressedEdwardsY;
+    /// # fn main() {
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    ///
+    /// // y = 2 is not the y-coordinate of a curve point
+    /// let mut bytes = [0u8; 32];
+    /// bytes[0] = 2;
+    ///
+    /// let decompressed = CompressedEdwardsY::batch_decompress(&[B.compress(), CompressedEdwardsY(bytes)]);
+    /// assert_eq!(decompressed, vec![Some(B), None]);
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn batch_decompress(compressed: &[CompressedEdwardsY]) -> Vec<Option<EdwardsPoint>> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            if compressed.len() >= PARALLEL_DECOMPRESS_THRESHOLD && rayon::current_num_threads() > 1 {
+                return compressed
+                    .par_iter()
+                    .with_min_len(PARALLEL_DECOMPRESS_MIN_CHUNK)
+                    .map(|c| c.decompress())
+                    .collect();
+            }
+        }
+
+        compressed.iter().map(|c| c.decompress()).collect()
+    }
+
+    /// Determine whether these bytes are the canonical encoding of
+    /// a curve point.
+    ///
+    /// `decompress` accepts \\(y\\)-coordinates which are not reduced
+    /// modulo \\(p\\), as well as a set sign bit when \\(x = 0\\), so
+    /// that some points have more than one accepted encoding.  This
+    /// returns `true` exactly when the bytes are the ones that
+    /// `EdwardsPoint::compress` would produce for the decoded point.
+    ///
+    /// This function runs in constant time.  It does not check
+    /// whether the bytes decode to a point at all; use `decompress`
+    /// for that.
+    pub fn is_canonical(&self) -> bool {
+        self.ct_is_canonical().unwrap_u8() == 1u8
+    }
+
+    /// Constant-time version of `is_canonical`.
+    pub(crate) fn ct_is_canonical(&self) -> Choice {
+        let Y = FieldElement::from_bytes(self.as_bytes());
+
+        // The y-coordinate must be fully reduced ...
+        let mut y_bytes = self.to_bytes();
+        y_bytes[31] &= 0x7f;
+        let y_is_reduced = Y.to_bytes().ct_eq(&y_bytes);
+
+        // ... and the sign bit must be clear when x = 0, which is
+        // the case exactly when y = 1 or y = -1.
+        let sign_bit = Choice::from(self.as_bytes()[31] >> 7);
+        let x_is_zero = Y.ct_eq(&FieldElement::one()) | Y.ct_eq(&FieldElement::minus_one());
+
+        y_is_reduced & !(x_is_zero & sign_bit)
     }
 }
 
+/// The batch size above which `CompressedEdwardsY::batch_decompress`
+/// is split across the rayon thread pool.
+#[cfg(feature = "parallel")]
+const PARALLEL_DECOMPRESS_THRESHOLD: usize = 1024;
+
+/// The smallest chunk of points decompressed by each rayon task.
+#[cfg(feature = "parallel")]
+const PARALLEL_DECOMPRESS_MIN_CHUNK: usize = 256;
+
 // ------------------------------------------------------------------------
 // Serde support
 // ------------------------------------------------------------------------
@@ -207,18 +640,23 @@ impl CompressedEdwardsY {
 // and decompression internally.  This means that users can create
 // structs containing `EdwardsPoint`s and use Serde's derived
 // serializers to serialize those structures.
+//
+// Human-readable formats (like JSON) get the compressed encoding as a
+// lowercase hex string; binary formats (like bincode) get raw bytes.
 
 #[cfg(feature = "serde")]
 use serde::{self, Serialize, Deserialize, Serializer, Deserializer};
 #[cfg(feature = "serde")]
 use serde::de::Visitor;
+#[cfg(feature = "serde")]
+use serde_hex;
 
 #[cfg(feature = "serde")]
 impl Serialize for EdwardsPoint {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer
     {
-        serializer.serialize_bytes(self.compress().as_bytes())
+        serde_hex::serialize(self.compress().as_bytes(), serializer)
     }
 }
 
@@ -227,7 +665,7 @@ impl Serialize for CompressedEdwardsY {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer
     {
-        serializer.serialize_bytes(self.as_bytes())
+        serde_hex::serialize(self.as_bytes(), serializer)
     }
 }
 
@@ -245,6 +683,13 @@ impl<'de> Deserialize<'de> for EdwardsPoint {
                 formatter.write_str("a valid point in Edwards y + sign format")
             }
 
+            fn visit_str<E>(self, v: &str) -> Result<EdwardsPoint, E>
+                where E: serde::de::Error
+            {
+                let bytes = serde_hex::decode(v, &self)?;
+                self.visit_bytes(&bytes)
+            }
+
             fn visit_bytes<E>(self, v: &[u8]) -> Result<EdwardsPoint, E>
                 where E: serde::de::Error
             {
@@ -260,7 +705,7 @@ impl<'de> Deserialize<'de> for EdwardsPoint {
             }
         }
 
-        deserializer.deserialize_bytes(EdwardsPointVisitor)
+        serde_hex::deserialize(deserializer, EdwardsPointVisitor)
     }
 }
 
@@ -278,6 +723,13 @@ impl<'de> Deserialize<'de> for CompressedEdwardsY {
                 formatter.write_str("32 bytes of data")
             }
 
+            fn visit_str<E>(self, v: &str) -> Result<CompressedEdwardsY, E>
+                where E: serde::de::Error
+            {
+                let bytes = serde_hex::decode(v, &self)?;
+                self.visit_bytes(&bytes)
+            }
+
             fn visit_bytes<E>(self, v: &[u8]) -> Result<CompressedEdwardsY, E>
                 where E: serde::de::Error
             {
@@ -291,7 +743,66 @@ impl<'de> Deserialize<'de> for CompressedEdwardsY {
             }
         }
 
-        deserializer.deserialize_bytes(CompressedEdwardsYVisitor)
+        serde_hex::deserialize(deserializer, CompressedEdwardsYVisitor)
+    }
+}
+
+// ------------------------------------------------------------------------
+// Group traits
+// ------------------------------------------------------------------------
+// Implements the `group` crate's traits, so that `EdwardsPoint` can be
+// used with code that is generic over `group::Group`.  Note that the
+// Edwards curve has cofactor 8, so this is not a prime-order group.
+
+#[cfg(feature = "group")]
+impl group::Group for EdwardsPoint {
+    type Scalar = Scalar;
+
+    fn random(mut rng: impl rand_core_06::RngCore) -> Self {
+        let mut repr = CompressedEdwardsY([0u8; 32]);
+        loop {
+            rng.fill_bytes(&mut repr.0);
+            if let Some(p) = repr.decompress() {
+                if !IsIdentity::is_identity(&p) {
+                    break p;
+                }
+            }
+        }
+    }
+
+    fn identity() -> Self {
+        Identity::identity()
+    }
+
+    fn generator() -> Self {
+        constants::ED25519_BASEPOINT_POINT
+    }
+
+    fn is_identity(&self) -> Choice {
+        self.ct_is_identity()
+    }
+
+    fn double(&self) -> Self {
+        self.double()
+    }
+}
+
+#[cfg(feature = "group")]
+impl group::GroupEncoding for EdwardsPoint {
+    type Repr = [u8; 32];
+
+    fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+        let (is_valid_y_coord, point) = CompressedEdwardsY(*bytes).ct_decompress();
+        CtOption::new(point, is_valid_y_coord)
+    }
+
+    fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+        // Just use the checked API; there are no checks we can skip.
+        Self::from_bytes(bytes)
+    }
+
+    fn to_bytes(&self) -> Self::Repr {
+        self.compress().to_bytes()
     }
 }
 
@@ -300,6 +811,14 @@ impl<'de> Deserialize<'de> for CompressedEdwardsY {
 // ------------------------------------------------------------------------
 
 /// An `EdwardsPoint` represents a point on the Edwards form of Curve25519.
+///
+/// Points are stored in extended twisted Edwards coordinates
+/// \\((X:Y:Z:T)\\), which are never normalized: the results of
+/// addition, scalar multiplication, and multiscalar multiplication
+/// are returned without any field inversion.  The only inversion
+/// happens when a point is encoded, e.g., by `compress`, so it is
+/// cheap to accumulate several results (for instance, of separate
+/// multiscalar multiplications) and compress only the final sum.
 #[derive(Copy, Clone)]
 #[allow(missing_docs)]
 pub struct EdwardsPoint {
@@ -313,12 +832,21 @@ pub struct EdwardsPoint {
 // Constructors
 // ------------------------------------------------------------------------
 
-impl Identity for CompressedEdwardsY {
-    fn identity() -> CompressedEdwardsY {
+impl CompressedEdwardsY {
+    /// The encoding of the identity point.
+    ///
+    /// This is the same as `CompressedEdwardsY::identity()`, but can be
+    /// used in `const` and `static` initializers.
+    pub const IDENTITY: CompressedEdwardsY =
         CompressedEdwardsY([1, 0, 0, 0, 0, 0, 0, 0,
                             0, 0, 0, 0, 0, 0, 0, 0,
                             0, 0, 0, 0, 0, 0, 0, 0,
-                            0, 0, 0, 0, 0, 0, 0, 0])
+                            0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+impl Identity for CompressedEdwardsY {
+    fn identity() -> CompressedEdwardsY {
+        CompressedEdwardsY::IDENTITY
     }
 }
 
@@ -341,16 +869,64 @@ impl CompressedEdwardsY {
 
         CompressedEdwardsY(tmp)
     }
+
+    /// Attempt to construct a `CompressedEdwardsY` from a slice of bytes.
+    ///
+    /// Unlike `from_slice`, this does not panic on malformed input.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TryFromSliceError` if the input `bytes` slice does
+    /// not have a length of 32.
+    pub fn try_from_slice(bytes: &[u8]) -> Result<CompressedEdwardsY, TryFromSliceError> {
+        <[u8; 32]>::try_from(bytes).map(CompressedEdwardsY)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for CompressedEdwardsY {
+    type Error = TryFromSliceError;
+
+    fn try_from(bytes: &'a [u8]) -> Result<CompressedEdwardsY, TryFromSliceError> {
+        CompressedEdwardsY::try_from_slice(bytes)
+    }
+}
+
+impl EdwardsPoint {
+    /// The identity point.
+    ///
+    /// This is the same as `EdwardsPoint::identity()`, but can be used
+    /// in `const` and `static` initializers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::traits::Identity;
+    ///
+    /// static TABLE: [EdwardsPoint; 4] = [EdwardsPoint::IDENTITY; 4];
+    ///
+    /// assert_eq!(TABLE[0], EdwardsPoint::identity());
+    /// ```
+    pub const IDENTITY: EdwardsPoint = EdwardsPoint {
+        X: FieldElement::zero(),
+        Y: FieldElement::one(),
+        Z: FieldElement::one(),
+        T: FieldElement::zero(),
+    };
+
+    /// Determine if this point is the identity, in constant time.
+    ///
+    /// Unlike `IsIdentity::is_identity`, which returns a `bool`, this
+    /// returns a `Choice`, so it can be used with secret points
+    /// without introducing a branch.
+    pub fn ct_is_identity(&self) -> Choice {
+        self.ct_eq(&EdwardsPoint::IDENTITY)
+    }
 }
 
 impl Identity for EdwardsPoint {
     fn identity() -> EdwardsPoint {
-        EdwardsPoint {
-            X: FieldElement::zero(),
-            Y: FieldElement::one(),
-            Z: FieldElement::one(),
-            T: FieldElement::zero(),
-        }
+        EdwardsPoint::IDENTITY
     }
 }
 
@@ -360,6 +936,21 @@ impl Default for EdwardsPoint {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl Zeroize for EdwardsPoint {
+    /// Reset this `EdwardsPoint` to the identity element.
+    ///
+    /// Setting every coordinate to zero would not give a valid point,
+    /// so the \\(X\\) and \\(T\\) coordinates are zeroed and the
+    /// \\(Y\\) and \\(Z\\) coordinates are set to one.
+    fn zeroize(&mut self) {
+        self.X.zeroize();
+        self.Y = FieldElement::one();
+        self.Z = FieldElement::one();
+        self.T.zeroize();
+    }
+}
+
 // ------------------------------------------------------------------------
 // Validity checks (for debugging, not CT)
 // ------------------------------------------------------------------------
@@ -388,6 +979,44 @@ impl ConditionallySelectable for EdwardsPoint {
     }
 }
 
+impl EdwardsPoint {
+    /// Select `points[index]` in constant time.
+    ///
+    /// Every entry of `points` is scanned and conditionally assigned,
+    /// so the memory access pattern and the running time are
+    /// \\(O(N)\\) in the length of the slice, independent of the
+    /// secret `index`.  This is intended as a building block for
+    /// custom windowed multiplications, generalizing the fixed-size
+    /// selection used internally by the lookup tables.
+    ///
+    /// # Return
+    ///
+    /// * `points[index]` if `index < points.len()`;
+    ///
+    /// * the identity point otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::traits::Identity;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let table = [B, B + B, B + B + B];
+    ///
+    /// assert_eq!(EdwardsPoint::conditional_select_from(&table, 1), B + B);
+    /// assert_eq!(EdwardsPoint::conditional_select_from(&table, 3), EdwardsPoint::identity());
+    /// ```
+    pub fn conditional_select_from(points: &[EdwardsPoint], index: u8) -> EdwardsPoint {
+        let mut result = EdwardsPoint::identity();
+        for (j, P) in points.iter().enumerate() {
+            result.conditional_assign(P, (index as usize).ct_eq(&j));
+        }
+        result
+    }
+}
+
 // ------------------------------------------------------------------------
 // Equality
 // ------------------------------------------------------------------------
@@ -413,6 +1042,40 @@ impl PartialEq for EdwardsPoint {
 
 impl Eq for EdwardsPoint {}
 
+impl EdwardsPoint {
+    /// Check in constant time whether `a[i] == b[i]` for every `i`.
+    ///
+    /// The per-element comparisons are folded into a single `Choice`
+    /// without an early exit, so the running time does not depend on
+    /// where, or whether, the slices differ.  This is the same as
+    /// comparing the slices with `subtle`'s `ConstantTimeEq`
+    /// implementation for `[T]`.
+    ///
+    /// # Return
+    ///
+    /// * `Choice(1)` if `a` and `b` have the same length and are
+    ///   equal element-wise;
+    ///
+    /// * `Choice(0)` otherwise.  Slices of different lengths are
+    ///   rejected immediately, since the lengths are not secret.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let a = [B, B + B];
+    ///
+    /// assert!(bool::from(EdwardsPoint::ct_eq_batch(&a, &[B, B.mul_by_pow_2(1)])));
+    /// assert!(!bool::from(EdwardsPoint::ct_eq_batch(&a, &[B, B])));
+    /// ```
+    pub fn ct_eq_batch(a: &[EdwardsPoint], b: &[EdwardsPoint]) -> Choice {
+        a.ct_eq(b)
+    }
+}
+
 // ------------------------------------------------------------------------
 // Point conversions
 // ------------------------------------------------------------------------
@@ -477,20 +1140,223 @@ impl EdwardsPoint {
 
     /// Compress this point to `CompressedEdwardsY` format.
     pub fn compress(&self) -> CompressedEdwardsY {
-        let recip = self.Z.invert();
-        let x = &self.X * &recip;
-        let y = &self.Y * &recip;
-        let mut s: [u8; 32];
-
-        s = y.to_bytes();
-        s[31] ^= x.is_negative().unwrap_u8() << 7;
-        CompressedEdwardsY(s)
+        self.compress_with_recip(&self.Z.invert())
     }
-}
 
-// ------------------------------------------------------------------------
-// Doubling
-// ------------------------------------------------------------------------
+    /// Compress a batch of points to `CompressedEdwardsY` format.
+    ///
+    /// This computes the same result as calling `compress` on each
+    /// point, but uses Montgomery's inversion trick to share a single
+    /// field inversion among all of the points, which is much faster
+    /// for large batches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # use curve25519_dalek::constants;
+    /// # use curve25519_dalek::edwards::EdwardsPoint;
+    /// # use curve25519_dalek::scalar::Scalar;
+    /// # fn main() {
+    /// let points: Vec<EdwardsPoint> = (1..8u64)
+    ///     .map(|i| &constants::ED25519_BASEPOINT_TABLE * &Scalar::from(i))
+    ///     .collect();
+    ///
+    /// let compressed = EdwardsPoint::compress_batch(&points);
+    ///
+    /// for (P, P_compressed) in points.iter().zip(compressed.iter()) {
+    ///     assert_eq!(P.compress(), *P_compressed);
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn compress_batch(points: &[EdwardsPoint]) -> Vec<CompressedEdwardsY> {
+        let mut recips: Vec<FieldElement> = points.iter().map(|P| P.Z).collect();
+        FieldElement::batch_invert(&mut recips);
+
+        points
+            .iter()
+            .zip(recips.iter())
+            .map(|(P, recip)| P.compress_with_recip(recip))
+            .collect()
+    }
+
+    /// Double-and-compress a batch of points.
+    ///
+    /// Given input points \\( P\_1, \ldots, P\_n, \\) this computes the
+    /// compressed encodings of their doubles \\( [2]P\_1, \ldots,
+    /// [2]P\_n \\), sharing a single field inversion among all of the
+    /// points as in `compress_batch`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # use curve25519_dalek::constants;
+    /// # use curve25519_dalek::edwards::EdwardsPoint;
+    /// # use curve25519_dalek::scalar::Scalar;
+    /// # fn main() {
+    /// let points: Vec<EdwardsPoint> = (1..8u64)
+    ///     .map(|i| &constants::ED25519_BASEPOINT_TABLE * &Scalar::from(i))
+    ///     .collect();
+    ///
+    /// let compressed = EdwardsPoint::double_and_compress_batch(&points);
+    ///
+    /// for (P, P2_compressed) in points.iter().zip(compressed.iter()) {
+    ///     assert_eq!((P + P).compress(), *P2_compressed);
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn double_and_compress_batch(points: &[EdwardsPoint]) -> Vec<CompressedEdwardsY> {
+        let doubles: Vec<EdwardsPoint> = points.iter().map(|P| P.double()).collect();
+        EdwardsPoint::compress_batch(&doubles)
+    }
+
+    /// Compress this point, given the inverse of its \\(Z\\) coordinate.
+    fn compress_with_recip(&self, recip: &FieldElement) -> CompressedEdwardsY {
+        let x = &self.X * recip;
+        let y = &self.Y * recip;
+        CompressedEdwardsY::from_y_and_sign(&y, x.is_negative())
+    }
+
+    /// Compute the affine coordinates \\((x, y)\\) of this point.
+    ///
+    /// This costs one field inversion.  The coordinates can be encoded
+    /// canonically with `FieldElement::to_bytes`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let (x, y) = B.to_affine();
+    ///
+    /// // The compressed encoding is y, with the sign of x in the top bit
+    /// let mut y_bytes = y.to_bytes();
+    /// y_bytes[31] |= x.is_negative().unwrap_u8() << 7;
+    /// assert_eq!(y_bytes, B.compress().to_bytes());
+    /// ```
+    pub fn to_affine(&self) -> (FieldElement, FieldElement) {
+        let recip = self.Z.invert();
+        (&self.X * &recip, &self.Y * &recip)
+    }
+
+    /// Encode this point as the 64 bytes \\(x \\| y\\) of its affine
+    /// coordinates, each in canonical little-endian form.
+    ///
+    /// This is twice the size of `compress`, but unlike
+    /// `CompressedEdwardsY::decompress`, decoding it with
+    /// `from_bytes_uncompressed` does not need a square root.
+    pub fn to_bytes_uncompressed(&self) -> [u8; 64] {
+        let (x, y) = self.to_affine();
+
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&x.to_bytes());
+        bytes[32..].copy_from_slice(&y.to_bytes());
+        bytes
+    }
+
+    /// Decode a point from the 64-byte encoding produced by
+    /// `to_bytes_uncompressed`.
+    ///
+    /// Returns `None` if either coordinate is not canonically encoded,
+    /// or if \\((x, y)\\) is not on the curve.  This only costs a few
+    /// field multiplications, which is much cheaper than decompression.
+    ///
+    /// Like `decompress`, this does not check that the point is in the
+    /// prime-order subgroup.
+    pub fn from_bytes_uncompressed(bytes: &[u8; 64]) -> Option<EdwardsPoint> {
+        let mut x_bytes = [0u8; 32];
+        let mut y_bytes = [0u8; 32];
+        x_bytes.copy_from_slice(&bytes[..32]);
+        y_bytes.copy_from_slice(&bytes[32..]);
+
+        let x = FieldElement::from_bytes(&x_bytes);
+        let y = FieldElement::from_bytes(&y_bytes);
+        let is_canonical = x.to_bytes().ct_eq(&x_bytes) & y.to_bytes().ct_eq(&y_bytes);
+
+        // Check the curve equation -x² + y² = 1 + dx²y².
+        let xx = x.square();
+        let yy = y.square();
+        let lhs = &yy - &xx;
+        let rhs = &FieldElement::one() + &(&constants::EDWARDS_D * &(&xx * &yy));
+        let is_on_curve = lhs.ct_eq(&rhs);
+
+        if (is_canonical & is_on_curve).unwrap_u8() != 1u8 { return None; }
+
+        Some(EdwardsPoint{ X: x, Y: y, Z: FieldElement::one(), T: &x * &y })
+    }
+
+    /// Map 32 bytes to an `EdwardsPoint` using the Elligator 2 map.
+    ///
+    /// The low 255 bits of `bytes` are interpreted as a field element
+    /// \\(r\\), which is mapped to the Montgomery curve with
+    /// `MontgomeryPoint::elligator_encode`, and the result is lifted
+    /// to the Edwards curve with the birational map.  The high bit of
+    /// `bytes` selects the sign of the \\(x\\)-coordinate.  The
+    /// \\(y\\)-coordinate is the same as that of the Elligator 2 map
+    /// for edwards25519 in RFC 9380.
+    ///
+    /// The output of a single call is not uniformly distributed,
+    /// but the sum of the outputs of two calls on independent uniformly
+    /// random inputs is statistically close to uniform on the curve.
+    ///
+    /// The resulting point is not necessarily in the prime-order
+    /// subgroup; use `mul_by_cofactor` to clear the torsion component
+    /// if required.
+    ///
+    /// This function runs in constant time.
+    pub fn from_uniform_bytes(bytes: &[u8; 32]) -> EdwardsPoint {
+        let r_0 = FieldElement::from_bytes(bytes);
+        let sign_bit = bytes[31] >> 7;
+
+        // The Elligator 2 map only produces points on the curve, and
+        // never u = -1, so the lift to the Edwards curve always exists.
+        MontgomeryPoint::elligator_encode(&r_0)
+            .to_edwards(sign_bit)
+            .expect("Elligator 2 output is on the curve")
+    }
+
+    /// Return an `EdwardsPoint` chosen uniformly at random from the
+    /// whole curve, using a user-provided RNG.
+    ///
+    /// # Implementation
+    ///
+    /// Applies `from_uniform_bytes` to two independent 32-byte strings
+    /// and adds the results, so that the output is statistically close
+    /// to uniform, and its discrete log with respect to any other point
+    /// is unknown.
+    ///
+    /// Since the curve has cofactor 8, the output is **not**
+    /// guaranteed to be torsion-free: it has a nonzero torsion
+    /// component with probability \\(7/8\\).  Use
+    /// `random_prime_order` for a point in the prime-order subgroup.
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> EdwardsPoint {
+        let mut r_1 = [0u8; 32];
+        let mut r_2 = [0u8; 32];
+        rng.fill_bytes(&mut r_1);
+        rng.fill_bytes(&mut r_2);
+
+        EdwardsPoint::from_uniform_bytes(&r_1) + EdwardsPoint::from_uniform_bytes(&r_2)
+    }
+
+    /// Return an `EdwardsPoint` chosen uniformly at random from the
+    /// prime-order subgroup, using a user-provided RNG.
+    ///
+    /// This is `random` followed by `mul_by_cofactor`, so the output
+    /// is always torsion-free.  Multiplying by the cofactor maps the
+    /// uniform distribution on the curve to the uniform distribution
+    /// on the prime-order subgroup.
+    pub fn random_prime_order<R: RngCore + CryptoRng>(rng: &mut R) -> EdwardsPoint {
+        EdwardsPoint::random(rng).mul_by_cofactor()
+    }
+}
+
+// ------------------------------------------------------------------------
+// Doubling
+// ------------------------------------------------------------------------
 
 impl EdwardsPoint {
     /// Add this point to itself.
@@ -537,6 +1403,102 @@ impl<'b> SubAssign<&'b EdwardsPoint> for EdwardsPoint {
 
 define_sub_assign_variants!(LHS = EdwardsPoint, RHS = EdwardsPoint);
 
+/// An `EdwardsPoint` precomputed for use as the right-hand side of
+/// many additions or subtractions.
+///
+/// Adding two `EdwardsPoint`s first converts the right-hand side to
+/// the form used by the addition formulas, which costs a field
+/// multiplication by \\(2d\\) among other things.  An
+/// `EdwardsCachedPoint` stores that form, so that adding the same point
+/// to many different points skips the conversion.
+///
+/// In an accumulation loop, keep the running sum as an `EdwardsPoint`
+/// and add or subtract cached points into it.  Each addition still
+/// finishes with a conversion back to extended coordinates, since the
+/// formulas need the \\(T\\) coordinate of the left-hand side, but
+/// that conversion is the cheapest form the next addition can use.
+///
+/// # Example
+///
+/// ```
+/// use curve25519_dalek::constants;
+/// use curve25519_dalek::edwards::EdwardsCachedPoint;
+///
+/// let B = constants::ED25519_BASEPOINT_POINT;
+/// let cached_B = EdwardsCachedPoint::from(&B);
+///
+/// let mut P = B;
+/// for _ in 0..4 {
+///     P += &cached_B;
+/// }
+/// assert_eq!(P, B + B + B + B + B);
+/// assert_eq!(P - &cached_B, B + B + B + B);
+/// ```
+// This wraps the backend point in a facade type so that it stays out
+// of the public API.
+#[derive(Copy, Clone)]
+pub struct EdwardsCachedPoint(ProjectiveNielsPoint);
+
+impl<'a> From<&'a EdwardsPoint> for EdwardsCachedPoint {
+    fn from(point: &'a EdwardsPoint) -> EdwardsCachedPoint {
+        EdwardsCachedPoint(point.to_projective_niels())
+    }
+}
+
+impl From<EdwardsPoint> for EdwardsCachedPoint {
+    fn from(point: EdwardsPoint) -> EdwardsCachedPoint {
+        EdwardsCachedPoint::from(&point)
+    }
+}
+
+impl<'b> Add<&'b EdwardsCachedPoint> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn add(self, other: &'b EdwardsCachedPoint) -> EdwardsPoint {
+        (self + &other.0).to_extended()
+    }
+}
+
+define_add_variants!(LHS = EdwardsPoint, RHS = EdwardsCachedPoint, Output = EdwardsPoint);
+
+impl<'b> AddAssign<&'b EdwardsCachedPoint> for EdwardsPoint {
+    fn add_assign(&mut self, _rhs: &'b EdwardsCachedPoint) {
+        *self = (self as &EdwardsPoint) + _rhs;
+    }
+}
+
+define_add_assign_variants!(LHS = EdwardsPoint, RHS = EdwardsCachedPoint);
+
+impl<'b> Sub<&'b EdwardsCachedPoint> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn sub(self, other: &'b EdwardsCachedPoint) -> EdwardsPoint {
+        (self - &other.0).to_extended()
+    }
+}
+
+define_sub_variants!(LHS = EdwardsPoint, RHS = EdwardsCachedPoint, Output = EdwardsPoint);
+
+impl<'b> SubAssign<&'b EdwardsCachedPoint> for EdwardsPoint {
+    fn sub_assign(&mut self, _rhs: &'b EdwardsCachedPoint) {
+        *self = (self as &EdwardsPoint) - _rhs;
+    }
+}
+
+define_sub_assign_variants!(LHS = EdwardsPoint, RHS = EdwardsCachedPoint);
+
+impl Neg for &EdwardsCachedPoint {
+    type Output = EdwardsCachedPoint;
+    fn neg(self) -> EdwardsCachedPoint {
+        EdwardsCachedPoint(-&self.0)
+    }
+}
+
+impl Neg for EdwardsCachedPoint {
+    type Output = EdwardsCachedPoint;
+    fn neg(self) -> EdwardsCachedPoint {
+        -&self
+    }
+}
+
 impl<T> Sum<T> for EdwardsPoint
 where
     T: Borrow<EdwardsPoint>
@@ -549,11 +1511,69 @@ where
     }
 }
 
+impl EdwardsPoint {
+    /// Decompress and sum an iterator of `CompressedEdwardsY`s.
+    ///
+    /// Returns `None` if any of the inputs fails to decompress (see
+    /// `CompressedEdwardsY::decompress`), and the sum otherwise.
+    /// Iteration stops at the first invalid input.
+    ///
+    /// Each point is decompressed separately, since decompression
+    /// cannot be batched (see `FieldElement::sqrt_ratio_i`).
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # use curve25519_dalek::constants;
+    /// # use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+    /// # fn main() {
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let points = [B.compress(), (B + B).compress()];
+    /// assert_eq!(EdwardsPoint::optional_sum(points.iter()), Some(B + B + B));
+    ///
+    /// // y = 2 is not the y-coordinate of a curve point
+    /// let mut bytes = [0u8; 32];
+    /// bytes[0] = 2;
+    /// let points = [B.compress(), CompressedEdwardsY(bytes)];
+    /// assert_eq!(EdwardsPoint::optional_sum(points.iter()), None);
+    /// # }
+    /// ```
+    pub fn optional_sum<I>(compressed: I) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<CompressedEdwardsY>,
+    {
+        compressed
+            .into_iter()
+            .try_fold(EdwardsPoint::identity(), |acc, c| c.borrow().decompress().map(|P| acc + P))
+    }
+}
+
 
 // ------------------------------------------------------------------------
 // Negation
 // ------------------------------------------------------------------------
 
+/// Negation of an `EdwardsPoint` negates \\(X\\) and \\(T\\) and leaves
+/// \\(Y\\) and \\(Z\\) unchanged.
+///
+/// Together with the `ConditionallySelectable` impl, this gives
+/// `EdwardsPoint` the blanket `subtle::ConditionallyNegatable` impl,
+/// so `P.conditional_negate(choice)` selects between \\(P\\) and
+/// \\(-P\\) in constant time:
+///
+/// ```
+/// # extern crate curve25519_dalek;
+/// # extern crate subtle;
+/// # use curve25519_dalek::constants;
+/// # use subtle::{Choice, ConditionallyNegatable};
+/// # fn main() {
+/// let B = constants::ED25519_BASEPOINT_POINT;
+///
+/// let mut P = B;
+/// P.conditional_negate(Choice::from(1));
+/// assert_eq!(P, -B);
+/// # }
+/// ```
 impl<'a> Neg for &'a EdwardsPoint {
     type Output = EdwardsPoint;
 
@@ -614,6 +1634,61 @@ impl<'a, 'b> Mul<&'b EdwardsPoint> for &'a Scalar {
     }
 }
 
+/// Precomputation for constant-time multiplication of a fixed
+/// `EdwardsPoint` by many scalars.
+///
+/// Each `&P * &s` builds a lookup table of small multiples of
+/// \\(P\\) before multiplying.  An `EdwardsScalarMulContext` builds
+/// that table once, so that multiplying the same point by many
+/// (possibly secret) scalars skips the rebuild.  The table is small,
+/// so this is cheap to create; for a point used across very many
+/// multiplications, an `EdwardsBasepointTable` is larger but faster.
+///
+/// ```
+/// # extern crate curve25519_dalek;
+/// # use curve25519_dalek::constants;
+/// # use curve25519_dalek::edwards::EdwardsScalarMulContext;
+/// # use curve25519_dalek::scalar::Scalar;
+/// # fn main() {
+/// let P = constants::ED25519_BASEPOINT_POINT * Scalar::from(7u64);
+/// let context = EdwardsScalarMulContext::new(&P);
+///
+/// for i in 1..4u64 {
+///     let s = Scalar::from(i);
+///     assert_eq!(&context * &s, P * s);
+/// }
+/// # }
+/// ```
+// This wraps the backend table in a facade type so that it stays out
+// of the public API.
+#[derive(Copy, Clone)]
+pub struct EdwardsScalarMulContext(scalar_mul::variable_base::VariableBaseTable);
+
+impl EdwardsScalarMulContext {
+    /// Precompute the lookup table for multiplying `point` by scalars.
+    pub fn new(point: &EdwardsPoint) -> EdwardsScalarMulContext {
+        EdwardsScalarMulContext(scalar_mul::variable_base::VariableBaseTable::new(point))
+    }
+}
+
+impl<'a, 'b> Mul<&'b Scalar> for &'a EdwardsScalarMulContext {
+    type Output = EdwardsPoint;
+
+    /// Constant-time scalar multiplication of the precomputed point.
+    fn mul(self, scalar: &'b Scalar) -> EdwardsPoint {
+        self.0.mul(scalar)
+    }
+}
+
+impl<'a, 'b> Mul<&'a EdwardsScalarMulContext> for &'b Scalar {
+    type Output = EdwardsPoint;
+
+    /// Constant-time scalar multiplication of the precomputed point.
+    fn mul(self, context: &'a EdwardsScalarMulContext) -> EdwardsPoint {
+        context * self
+    }
+}
+
 // ------------------------------------------------------------------------
 // Multiscalar Multiplication impls
 // ------------------------------------------------------------------------
@@ -621,6 +1696,23 @@ impl<'a, 'b> Mul<&'b EdwardsPoint> for &'a Scalar {
 // These use the iterator's size hint and the target settings to
 // forward to a specific backend implementation.
 
+/// Check that the scalars and points of a multiscalar multiplication
+/// have the same length, as given by their size hints, and return it.
+#[cfg(feature = "alloc")]
+fn multiscalar_len<I: Iterator, J: Iterator>(scalars: &I, points: &J) -> usize {
+    // Lower and upper bounds on iterators
+    let (s_lo, s_hi) = scalars.size_hint();
+    let (p_lo, p_hi) = points.size_hint();
+
+    // They should all be equal.  Only checking the lower bounds would
+    // let inputs of different lengths be silently truncated by `zip`.
+    assert_eq!(s_lo, p_lo);
+    assert_eq!(s_hi, Some(s_lo));
+    assert_eq!(p_hi, Some(p_lo));
+
+    s_lo
+}
+
 #[cfg(feature = "alloc")]
 impl MultiscalarMul for EdwardsPoint {
     type Point = EdwardsPoint;
@@ -632,27 +1724,49 @@ impl MultiscalarMul for EdwardsPoint {
         J: IntoIterator,
         J::Item: Borrow<EdwardsPoint>,
     {
-        // Sanity-check lengths of input iterators
-        let mut scalars = scalars.into_iter();
-        let mut points = points.into_iter();
+        let scalars = scalars.into_iter();
+        let points = points.into_iter();
 
-        // Lower and upper bounds on iterators
-        let (s_lo, s_hi) = scalars.by_ref().size_hint();
-        let (p_lo, p_hi) = points.by_ref().size_hint();
-
-        // They should all be equal
-        assert_eq!(s_lo, p_lo);
-        assert_eq!(s_hi, Some(s_lo));
-        assert_eq!(p_hi, Some(p_lo));
-
-        // Now we know there's a single size.  When we do
+        // Sanity-check lengths of input iterators.  When we do
         // size-dependent algorithm dispatch, use this as the hint.
-        let _size = s_lo;
+        let _size = multiscalar_len(&scalars, &points);
 
         scalar_mul::straus::Straus::multiscalar_mul(scalars, points)
     }
 }
 
+impl EdwardsPoint {
+    /// Compute \\( s_1 P_1 + \cdots + s_N P_N \\) in constant time,
+    /// for a fixed number \\(N\\) of terms.
+    ///
+    /// This gives the same result as `EdwardsPoint::multiscalar_mul`,
+    /// but stores all of its temporaries on the stack, so it is
+    /// available without the `alloc` feature.  It uses a little over 1KB of
+    /// stack per term, so it is intended for small \\(N\\).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let P = B * Scalar::from(7u64);
+    /// let a = Scalar::from(3u64);
+    /// let b = Scalar::from(5u64);
+    ///
+    /// let Q = EdwardsPoint::multiscalar_mul_array(&[a, b], &[B, P]);
+    /// assert_eq!(Q, B * a + P * b);
+    /// ```
+    pub fn multiscalar_mul_array<const N: usize>(
+        scalars: &[Scalar; N],
+        points: &[EdwardsPoint; N],
+    ) -> EdwardsPoint {
+        scalar_mul::straus_array::mul(scalars, points)
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl VartimeMultiscalarMul for EdwardsPoint {
     type Point = EdwardsPoint;
@@ -663,130 +1777,919 @@ impl VartimeMultiscalarMul for EdwardsPoint {
         I::Item: Borrow<Scalar>,
         J: IntoIterator<Item = Option<EdwardsPoint>>,
     {
-        // Sanity-check lengths of input iterators
-        let mut scalars = scalars.into_iter();
-        let mut points = points.into_iter();
-
-        // Lower and upper bounds on iterators
-        let (s_lo, s_hi) = scalars.by_ref().size_hint();
-        let (p_lo, p_hi) = points.by_ref().size_hint();
+        let scalars = scalars.into_iter();
+        let points = points.into_iter();
+
+        // Sanity-check lengths of input iterators, and use the length
+        // as the hint to decide which algorithm to use.
+        let size = multiscalar_len(&scalars, &points);
+
+        #[cfg(feature = "parallel")]
+        {
+            let threads = rayon::current_num_threads();
+            if size >= PARALLEL_PIPPENGER_THRESHOLD && threads > 1 {
+                let scalars: Vec<Scalar> = scalars.map(|s| *s.borrow()).collect();
+                let points: Vec<EdwardsPoint> = points.collect::<Option<_>>()?;
+                let chunk_size = cmp::max(size.div_ceil(threads), PARALLEL_PIPPENGER_MIN_CHUNK);
+                return Some(par_pippenger(&scalars, &points, chunk_size));
+            }
+        }
 
-        // They should all be equal
-        assert_eq!(s_lo, p_lo);
-        assert_eq!(s_hi, Some(s_lo));
-        assert_eq!(p_hi, Some(p_lo));
+        MultiscalarMulAlgorithm::default().optional_multiscalar_mul(size, scalars, points)
+    }
+}
 
-        // Now we know there's a single size.
-        // Use this as the hint to decide which algorithm to use.
-        let size = s_lo;
+#[cfg(feature = "alloc")]
+impl EdwardsPoint {
+    /// Compute the sum of products \\( \sum\_i s\_i P\_i \\) of
+    /// `scalars` and `points`, in constant time.
+    ///
+    /// This is the same as `EdwardsPoint::multiscalar_mul`, but takes
+    /// slices rather than iterators.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scalars` and `points` have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let scalars = [Scalar::from(3u64), Scalar::from(5u64)];
+    /// let points = [B, B + B];
+    ///
+    /// let P = EdwardsPoint::sum_of_products(&scalars, &points);
+    /// assert_eq!(P, B * Scalar::from(13u64));
+    /// ```
+    pub fn sum_of_products(scalars: &[Scalar], points: &[EdwardsPoint]) -> EdwardsPoint {
+        EdwardsPoint::multiscalar_mul(scalars, points)
+    }
 
-        if size < 190 {
-            scalar_mul::straus::Straus::optional_multiscalar_mul(scalars, points)
-        } else {
-            scalar_mul::pippenger::Pippenger::optional_multiscalar_mul(scalars, points)
-        }
+    /// Compute the sum of products \\( \sum\_i s\_i P\_i \\) of
+    /// `scalars` and `points`, in variable time.
+    ///
+    /// This is the same as `EdwardsPoint::vartime_multiscalar_mul`,
+    /// but takes slices rather than iterators.  It must only be used
+    /// with public scalars and points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scalars` and `points` have different lengths.
+    pub fn vartime_sum_of_products(scalars: &[Scalar], points: &[EdwardsPoint]) -> EdwardsPoint {
+        EdwardsPoint::vartime_multiscalar_mul(scalars, points)
     }
 }
 
-/// Precomputation for variable-time multiscalar multiplication with `EdwardsPoint`s.
-// This wraps the inner implementation in a facade type so that we can
-// decouple stability of the inner type from the stability of the
-// outer type.
+/// The input size at which variable-time multiscalar multiplication
+/// switches from Straus' algorithm to Pippenger's algorithm.
+///
+/// This crossover was chosen by benchmarking, but the best value
+/// depends on the target; use `MultiscalarMulAlgorithm` with
+/// `EdwardsPoint::vartime_multiscalar_mul_with_params` to choose a
+/// different one.
+pub const PIPPENGER_THRESHOLD: usize = 190;
+
+/// The largest bit length of the scalars which
+/// `EdwardsPoint::vartime_multiscalar_mul_with_scalar_sizes` multiplies
+/// separately from the full-width ones.
 #[cfg(feature = "alloc")]
-pub struct VartimeEdwardsPrecomputation(scalar_mul::precomputed_straus::VartimePrecomputedStraus);
+const SMALL_SCALAR_BITS: usize = 128;
 
+/// The algorithm used for variable-time multiscalar multiplication,
+/// for use with `EdwardsPoint::vartime_multiscalar_mul_with_params`.
+///
+/// Straus' algorithm is faster for small inputs, and Pippenger's
+/// algorithm is faster for large inputs.  The default is
+/// `Threshold(PIPPENGER_THRESHOLD)`, which is what
+/// `vartime_multiscalar_mul` uses.
 #[cfg(feature = "alloc")]
-impl VartimePrecomputedMultiscalarMul for VartimeEdwardsPrecomputation {
-    type Point = EdwardsPoint;
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MultiscalarMulAlgorithm {
+    /// Use Straus' algorithm for fewer than this many inputs, and
+    /// Pippenger's algorithm otherwise.
+    Threshold(usize),
+    /// Always use Straus' algorithm.
+    Straus,
+    /// Always use Pippenger's algorithm.
+    Pippenger,
+    /// Always use Pippenger's algorithm, with the given window width
+    /// `w`, instead of choosing it from the number of inputs.
+    ///
+    /// The scalars are split into \\(\lceil 256/w \rceil\\) windows
+    /// of `w` bits, and each window uses \\(2\^{w-1}\\) buckets.  The
+    /// default choice is `w = 6` for fewer than 500 inputs, `w = 7`
+    /// for fewer than 800 inputs, and `w = 8` otherwise.
+    ///
+    /// The window width must be between 4 and 8, otherwise the
+    /// multiplication panics.
+    PippengerWithWindow(usize),
+}
 
-    fn new<I>(static_points: I) -> Self
-    where
-        I: IntoIterator,
-        I::Item: Borrow<Self::Point>,
-    {
-        Self(scalar_mul::precomputed_straus::VartimePrecomputedStraus::new(static_points))
+#[cfg(feature = "alloc")]
+impl Default for MultiscalarMulAlgorithm {
+    fn default() -> MultiscalarMulAlgorithm {
+        MultiscalarMulAlgorithm::Threshold(PIPPENGER_THRESHOLD)
     }
+}
 
-    fn optional_mixed_multiscalar_mul<I, J, K>(
-        &self,
-        static_scalars: I,
-        dynamic_scalars: J,
-        dynamic_points: K,
-    ) -> Option<Self::Point>
+#[cfg(feature = "alloc")]
+impl MultiscalarMulAlgorithm {
+    /// Returns `true` if Pippenger's algorithm should be used for
+    /// `size` inputs.
+    fn use_pippenger(&self, size: usize) -> bool {
+        match *self {
+            MultiscalarMulAlgorithm::Threshold(threshold) => size >= threshold,
+            MultiscalarMulAlgorithm::Straus => false,
+            MultiscalarMulAlgorithm::Pippenger => true,
+            MultiscalarMulAlgorithm::PippengerWithWindow(_) => true,
+        }
+    }
+
+    fn optional_multiscalar_mul<I, J>(&self, size: usize, scalars: I, points: J) -> Option<EdwardsPoint>
     where
         I: IntoIterator,
         I::Item: Borrow<Scalar>,
-        J: IntoIterator,
-        J::Item: Borrow<Scalar>,
-        K: IntoIterator<Item = Option<Self::Point>>,
+        J: IntoIterator<Item = Option<EdwardsPoint>>,
     {
-        self.0
-            .optional_mixed_multiscalar_mul(static_scalars, dynamic_scalars, dynamic_points)
+        if let MultiscalarMulAlgorithm::PippengerWithWindow(w) = *self {
+            scalar_mul::pippenger::Pippenger::optional_multiscalar_mul_with_window(w, scalars, points)
+        } else if self.use_pippenger(size) {
+            scalar_mul::pippenger::Pippenger::optional_multiscalar_mul(scalars, points)
+        } else {
+            scalar_mul::straus::Straus::optional_multiscalar_mul(scalars, points)
+        }
     }
 }
 
-impl EdwardsPoint {
-    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
-    #[cfg(feature = "stage2_build")]
-    pub fn vartime_double_scalar_mul_basepoint(
-        a: &Scalar,
-        A: &EdwardsPoint,
-        b: &Scalar,
-    ) -> EdwardsPoint {
-        scalar_mul::vartime_double_base::mul(a, A, b)
-    }
+/// The input size above which variable-time multiscalar
+/// multiplication is split across the rayon thread pool.
+#[cfg(feature = "parallel")]
+const PARALLEL_PIPPENGER_THRESHOLD: usize = 4096;
+
+/// The smallest chunk of inputs given to each parallel Pippenger
+/// call, so that each chunk is still large enough for Pippenger's
+/// algorithm to be efficient.
+#[cfg(feature = "parallel")]
+const PARALLEL_PIPPENGER_MIN_CHUNK: usize = 1024;
+
+/// Compute a multiscalar multiplication by splitting the inputs into
+/// chunks of `chunk_size`, running Pippenger's algorithm on each
+/// chunk in the rayon thread pool, and summing the partial results.
+#[cfg(feature = "parallel")]
+fn par_pippenger(scalars: &[Scalar], points: &[EdwardsPoint], chunk_size: usize) -> EdwardsPoint {
+    use rayon::prelude::*;
+
+    scalars
+        .par_chunks(chunk_size)
+        .zip(points.par_chunks(chunk_size))
+        .map(|(scalars, points)| {
+            scalar_mul::pippenger::Pippenger::vartime_multiscalar_mul(scalars, points)
+        })
+        .reduce(EdwardsPoint::identity, |acc, P| acc + P)
 }
 
-/// A precomputed table of multiples of a basepoint, for accelerating
-/// fixed-base scalar multiplication.  One table, for the Ed25519
-/// basepoint, is provided in the `constants` module.
+/// Reusable scratch space for variable-time multiscalar multiplication
+/// with `EdwardsPoint`s.
 ///
-/// The basepoint tables are reasonably large (30KB), so they should
-/// probably be boxed.
-#[derive(Clone)]
-pub struct EdwardsBasepointTable(pub(crate) [LookupTable<AffineNielsPoint>; 32]);
+/// Passing the same `MsmScratch` to repeated calls of
+/// [`EdwardsPoint::vartime_multiscalar_mul_with_scratch`] reuses its
+/// buffers instead of allocating fresh ones for each call.  The
+/// scratch space only affects memory behaviour: the results are
+/// identical to those of `vartime_multiscalar_mul`.
+///
+/// [`EdwardsPoint::vartime_multiscalar_mul_with_scratch`]: struct.EdwardsPoint.html#method.vartime_multiscalar_mul_with_scratch
+// Like `VartimeEdwardsPrecomputation`, this wraps the backend types in
+// a facade so that they stay out of the public API.
+#[cfg(feature = "alloc")]
+pub struct MsmScratch {
+    straus: scalar_mul::straus::StrausScratch,
+    pippenger: scalar_mul::pippenger::PippengerScratch,
+}
 
-impl EdwardsBasepointTable {
-    /// The computation uses Pippeneger's algorithm, as described on
-    /// page 13 of the Ed25519 paper.  Write the scalar \\(a\\) in radix \\(16\\) with
-    /// coefficients in \\([-8,8)\\), i.e.,
-    /// $$
-    ///     a = a\_0 + a\_1 16\^1 + \cdots + a\_{63} 16\^{63},
-    /// $$
-    /// with \\(-8 \leq a_i < 8\\), \\(-8 \leq a\_{63} \leq 8\\).  Then
+#[cfg(feature = "alloc")]
+impl MsmScratch {
+    /// Create scratch space preallocated for multiscalar
+    /// multiplications of up to `max_size` terms.
+    ///
+    /// Larger inputs are still accepted; the buffers grow as needed
+    /// and keep their capacity for later calls.
+    pub fn new(max_size: usize) -> MsmScratch {
+        // Only the algorithm that would be selected for inputs of
+        // `max_size` terms or fewer needs storage up front.
+        let (straus_size, pippenger_size) = if max_size < PIPPENGER_THRESHOLD {
+            (max_size, 0)
+        } else {
+            (PIPPENGER_THRESHOLD - 1, max_size)
+        };
+        MsmScratch {
+            straus: scalar_mul::straus::StrausScratch::with_capacity(straus_size),
+            pippenger: scalar_mul::pippenger::PippengerScratch::with_capacity(pippenger_size),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EdwardsPoint {
+    /// Given an iterator of public scalars and an iterator of
+    /// points, compute
     /// $$
-    ///     a B = a\_0 B + a\_1 16\^1 B + \cdots + a\_{63} 16\^{63} B.
+    /// Q = c\_1 P\_1 + \cdots + c\_n P\_n,
     /// $$
-    /// Grouping even and odd coefficients gives
+    /// in variable time, using `scratch` for temporary storage.
+    ///
+    /// This computes the same result as
+    /// `EdwardsPoint::vartime_multiscalar_mul`, but reuses the
+    /// buffers in `scratch` rather than allocating on every call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::{EdwardsPoint, MsmScratch};
+    /// use curve25519_dalek::scalar::Scalar;
+    /// use curve25519_dalek::traits::VartimeMultiscalarMul;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let scalars = [Scalar::from(3u64), Scalar::from(5u64)];
+    /// let points = [B, B + B];
+    ///
+    /// let mut scratch = MsmScratch::new(2);
+    /// let P = EdwardsPoint::vartime_multiscalar_mul_with_scratch(&mut scratch, &scalars, &points);
+    ///
+    /// assert_eq!(P, EdwardsPoint::vartime_multiscalar_mul(&scalars, &points));
+    /// assert_eq!(P, B * Scalar::from(13u64));
+    /// ```
+    pub fn vartime_multiscalar_mul_with_scratch<I, J>(
+        scratch: &mut MsmScratch,
+        scalars: I,
+        points: J,
+    ) -> EdwardsPoint
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator,
+        J::Item: Borrow<EdwardsPoint>,
+    {
+        let scalars = scalars.into_iter();
+        let points = points.into_iter().map(|P| Some(*P.borrow()));
+
+        let size = multiscalar_len(&scalars, &points);
+
+        let result = if size < PIPPENGER_THRESHOLD {
+            scratch.straus.optional_multiscalar_mul(scalars, points)
+        } else {
+            scratch.pippenger.optional_multiscalar_mul(scalars, points)
+        };
+
+        result.unwrap()
+    }
+
+    /// Given an iterator of public scalars and an iterator of
+    /// points, compute
+    /// $$
+    /// Q = c\_1 P\_1 + \cdots + c\_n P\_n,
+    /// $$
+    /// in variable time, using the given `algorithm`.
+    ///
+    /// This computes the same result as
+    /// `EdwardsPoint::vartime_multiscalar_mul`, but lets the caller
+    /// choose between Straus' and Pippenger's algorithms, for
+    /// instance to tune the crossover for their target.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::{EdwardsPoint, MultiscalarMulAlgorithm};
+    /// use curve25519_dalek::scalar::Scalar;
+    /// use curve25519_dalek::traits::VartimeMultiscalarMul;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let scalars = [Scalar::from(3u64), Scalar::from(5u64)];
+    /// let points = [B, B + B];
+    ///
+    /// let P = EdwardsPoint::vartime_multiscalar_mul_with_params(
+    ///     MultiscalarMulAlgorithm::Pippenger,
+    ///     &scalars,
+    ///     &points,
+    /// );
+    ///
+    /// assert_eq!(P, EdwardsPoint::vartime_multiscalar_mul(&scalars, &points));
+    /// ```
+    pub fn vartime_multiscalar_mul_with_params<I, J>(
+        algorithm: MultiscalarMulAlgorithm,
+        scalars: I,
+        points: J,
+    ) -> EdwardsPoint
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator,
+        J::Item: Borrow<EdwardsPoint>,
+    {
+        let scalars = scalars.into_iter();
+        let points = points.into_iter().map(|P| Some(*P.borrow()));
+
+        let size = multiscalar_len(&scalars, &points);
+
+        algorithm
+            .optional_multiscalar_mul(size, scalars, points)
+            .unwrap()
+    }
+
+    /// Given an iterator of public scalars and an iterator of
+    /// points, compute
+    /// $$
+    /// Q = c\_1 P\_1 + \cdots + c\_n P\_n,
+    /// $$
+    /// in variable time, using Pippenger's algorithm with window
+    /// width `w`, in a single pass over the inputs.
+    ///
+    /// Unlike `EdwardsPoint::vartime_multiscalar_mul`, this does not
+    /// collect the inputs, so it is suitable for very large inputs
+    /// which are streamed rather than held in memory.  Instead, it
+    /// keeps \\(2\^{w-1}\\) buckets for each of the
+    /// \\(\lceil 256/w \rceil\\) windows, about 220KB for `w = 6`
+    /// and 680KB for `w = 8`, regardless of the number of terms.  The
+    /// iterators do not need to be `Clone` or to report their length.
+    ///
+    /// Each term costs about \\(256/w\\) additions, so larger
+    /// windows are faster for large inputs.
+    ///
+    /// # Panics
+    ///
+    /// If `w` is not between 4 and 8, or if `scalars` and `points`
+    /// have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    ///
+    /// // Neither iterator knows its length in advance.
+    /// let scalars = (1..1000u64).map(Scalar::from).take_while(|_| true);
+    /// let points = (1..1000u64).map(|_| B).take_while(|_| true);
+    ///
+    /// let P = EdwardsPoint::vartime_multiscalar_mul_streaming(8, scalars, points);
+    /// assert_eq!(P, B * Scalar::from(999u64 * 1000 / 2));
+    /// ```
+    pub fn vartime_multiscalar_mul_streaming<I, J>(
+        w: usize,
+        scalars: I,
+        points: J,
+    ) -> EdwardsPoint
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator,
+        J::Item: Borrow<EdwardsPoint>,
+    {
+        let points = points.into_iter().map(|P| Some(*P.borrow()));
+
+        scalar_mul::pippenger::Pippenger::optional_multiscalar_mul_streaming(w, scalars, points)
+            .unwrap()
+    }
+
+    /// Given an iterator of public scalars, an iterator of points, and
+    /// an iterator of bit lengths, compute
+    /// $$
+    /// Q = c\_1 P\_1 + \cdots + c\_n P\_n,
+    /// $$
+    /// in variable time, where each scalar \\(c\_i\\) is less than
+    /// \\(2\^{b\_i}\\) for the corresponding bit length \\(b\_i\\).
+    ///
+    /// This computes the same result as
+    /// `EdwardsPoint::vartime_multiscalar_mul`, but uses the bit
+    /// lengths to speed up inputs where many of the scalars are small,
+    /// as in Bulletproofs verification.  When there are enough terms
+    /// with scalars of at most 128 bits to use Pippenger's algorithm,
+    /// they are multiplied separately, skipping the windows above
+    /// their largest bit length, and the other terms are multiplied
+    /// as usual.  Otherwise, all of the terms are multiplied together.
+    ///
+    /// # Panics
+    ///
+    /// If the iterators have different lengths, or if a scalar is not
+    /// less than \\(2\^b\\) for its bit length \\(b\\).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::scalar::Scalar;
+    /// use curve25519_dalek::traits::VartimeMultiscalarMul;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let scalars = [Scalar::from(3u64), -Scalar::one()];
+    /// let points = [B, B + B];
+    ///
+    /// let P = EdwardsPoint::vartime_multiscalar_mul_with_scalar_sizes(&scalars, &points, vec![2, 256]);
+    ///
+    /// assert_eq!(P, EdwardsPoint::vartime_multiscalar_mul(&scalars, &points));
+    /// ```
+    pub fn vartime_multiscalar_mul_with_scalar_sizes<I, J, K>(
+        scalars: I,
+        points: J,
+        bit_lengths: K,
+    ) -> EdwardsPoint
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator,
+        J::Item: Borrow<EdwardsPoint>,
+        K: IntoIterator<Item = usize>,
+    {
+        let scalars: Vec<Scalar> = scalars.into_iter().map(|c| *c.borrow()).collect();
+        let points: Vec<EdwardsPoint> = points.into_iter().map(|P| *P.borrow()).collect();
+        let bit_lengths: Vec<usize> = bit_lengths.into_iter().collect();
+
+        assert_eq!(scalars.len(), points.len());
+        assert_eq!(scalars.len(), bit_lengths.len());
+
+        let mut small_terms = Vec::new();
+        let mut large_terms = Vec::new();
+        let mut small_bits = 0;
+        for ((c, P), &bits) in scalars.iter().zip(points.iter()).zip(bit_lengths.iter()) {
+            assert!(
+                c.bits().skip(bits).all(|bit| !bit),
+                "scalar does not fit in its bit length"
+            );
+            if bits <= SMALL_SCALAR_BITS {
+                small_terms.push((c, P));
+                small_bits = cmp::max(small_bits, bits);
+            } else {
+                large_terms.push((c, P));
+            }
+        }
+
+        if small_terms.len() < PIPPENGER_THRESHOLD {
+            return EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+        }
+
+        let small = scalar_mul::pippenger::Pippenger::optional_multiscalar_mul_with_max_bits(
+            small_bits,
+            small_terms.iter().map(|&(c, _)| c),
+            small_terms.iter().map(|&(_, P)| Some(*P)),
+        );
+        let large = EdwardsPoint::vartime_multiscalar_mul(
+            large_terms.iter().map(|&(c, _)| c),
+            large_terms.iter().map(|&(_, P)| P),
+        );
+
+        small.unwrap() + large
+    }
+
+    /// Given several vectors of public scalars and one slice of points,
+    /// compute
+    /// $$
+    /// Q\_j = c\_{j,1} P\_1 + \cdots + c\_{j,n} P\_n
+    /// $$
+    /// in variable time for each vector of scalars
+    /// \\((c\_{j,1}, \ldots, c\_{j,n})\\) in `scalar_vecs`.
+    ///
+    /// This computes the same results as calling
+    /// `EdwardsPoint::vartime_multiscalar_mul` once for each vector,
+    /// but when Straus' algorithm is used, the tables of multiples of
+    /// each point are built once and shared between the vectors.  The
+    /// doublings and additions depend on the scalars, so they are
+    /// still done once per vector.  Pippenger's algorithm has no work
+    /// which depends only on the points, so for inputs large enough to
+    /// use it, the vectors are multiplied separately.
+    ///
+    /// For many vectors with the same points, a
+    /// `VartimeEdwardsPrecomputation` builds larger tables which are
+    /// faster to use, but take longer to build.
+    ///
+    /// # Panics
+    ///
+    /// If any of the scalar vectors has a different length from `points`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let points = [B, B + B];
+    /// let a = [Scalar::from(3u64), Scalar::from(5u64)];
+    /// let b = [Scalar::from(1u64), Scalar::from(2u64)];
+    ///
+    /// let Q = EdwardsPoint::vartime_multiscalar_mul_batched(&[&a, &b], &points);
+    ///
+    /// assert_eq!(Q, vec![B * Scalar::from(13u64), B * Scalar::from(5u64)]);
+    /// ```
+    pub fn vartime_multiscalar_mul_batched(
+        scalar_vecs: &[&[Scalar]],
+        points: &[EdwardsPoint],
+    ) -> Vec<EdwardsPoint> {
+        for scalars in scalar_vecs.iter() {
+            assert_eq!(scalars.len(), points.len());
+        }
+
+        if MultiscalarMulAlgorithm::default().use_pippenger(points.len()) {
+            return scalar_vecs
+                .iter()
+                .map(|scalars| EdwardsPoint::vartime_multiscalar_mul(*scalars, points))
+                .collect();
+        }
+
+        scalar_mul::straus::StrausScratch::default().vartime_multiscalar_mul_batched(scalar_vecs, points)
+    }
+
+    /// Given an iterator of public scalars and an iterator of
+    /// compressed points, compute
+    /// $$
+    /// Q = c\_1 P\_1 + \cdots + c\_n P\_n,
+    /// $$
+    /// in variable time, or return `None` if any of the points fails
+    /// to decompress.
+    ///
+    /// The points are decompressed as they are consumed by the
+    /// multiscalar multiplication, so this avoids collecting the
+    /// decompressed points into an intermediate buffer, and stops at
+    /// the first invalid point.  Decompression cannot be batched (see
+    /// `FieldElement::sqrt_ratio_i`), so the cost of decompression is
+    /// the same as calling `decompress` on each point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let scalars = [Scalar::from(3u64), Scalar::from(5u64)];
+    /// let points = [B.compress(), (B + B).compress()];
+    ///
+    /// let P = EdwardsPoint::vartime_multiscalar_mul_compressed(&scalars, &points);
+    ///
+    /// assert_eq!(P, Some(B * Scalar::from(13u64)));
+    /// ```
+    pub fn vartime_multiscalar_mul_compressed<I, J>(
+        scalars: I,
+        points: J,
+    ) -> Option<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator,
+        J::Item: Borrow<CompressedEdwardsY>,
+    {
+        EdwardsPoint::optional_multiscalar_mul(
+            scalars,
+            points.into_iter().map(|P| P.borrow().decompress()),
+        )
+    }
+
+    /// Check in constant time whether
+    /// $$
+    /// c\_1 P\_1 + \cdots + c\_n P\_n = T,
+    /// $$
+    /// where \\(T\\) is the `target`.
+    ///
+    /// Rather than computing the left-hand side and comparing it with
+    /// \\(T\\), this appends the term \\(1 \cdot (-T)\\) to the linear
+    /// combination and checks whether the result is the identity, so
+    /// the target costs one more term of the multiscalar
+    /// multiplication rather than a separate comparison.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let scalars = [Scalar::from(3u64), Scalar::from(5u64)];
+    /// let points = [B, B + B];
+    ///
+    /// let T = B * Scalar::from(13u64);
+    /// assert_eq!(EdwardsPoint::check_linear_combination(&scalars, &points, &T).unwrap_u8(), 1);
+    /// assert_eq!(EdwardsPoint::check_linear_combination(&scalars, &points, &B).unwrap_u8(), 0);
+    /// ```
+    pub fn check_linear_combination<I, J>(scalars: I, points: J, target: &EdwardsPoint) -> Choice
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator,
+        J::Item: Borrow<EdwardsPoint>,
+    {
+        let scalars = scalars
+            .into_iter()
+            .map(|c| *c.borrow())
+            .chain(iter::once(Scalar::one()));
+        // Negate the point rather than the scalar: -1 mod l is not -1
+        // when the target has a torsion component.
+        let points = points
+            .into_iter()
+            .map(|P| *P.borrow())
+            .chain(iter::once(-target));
+
+        EdwardsPoint::multiscalar_mul(scalars, points).ct_eq(&EdwardsPoint::identity())
+    }
+
+    /// Check in variable time whether
+    /// $$
+    /// c\_1 P\_1 + \cdots + c\_n P\_n = T,
+    /// $$
+    /// where \\(T\\) is the `target`.
+    ///
+    /// This is the variable-time version of `check_linear_combination`,
+    /// for use with public scalars, such as when verifying proofs.
+    pub fn vartime_check_linear_combination<I, J>(
+        scalars: I,
+        points: J,
+        target: &EdwardsPoint,
+    ) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator,
+        J::Item: Borrow<EdwardsPoint>,
+    {
+        let scalars = scalars
+            .into_iter()
+            .map(|c| *c.borrow())
+            .chain(iter::once(Scalar::one()));
+        // Negate the point rather than the scalar: -1 mod l is not -1
+        // when the target has a torsion component.
+        let points = points
+            .into_iter()
+            .map(|P| *P.borrow())
+            .chain(iter::once(-target));
+
+        EdwardsPoint::vartime_multiscalar_mul(scalars, points).is_identity()
+    }
+}
+
+/// Collects the terms of a variable-time multiscalar multiplication
+/// one at a time, for callers which don't have all of the scalars and
+/// points up front.
+///
+/// The terms are only stored by `push`; the multiplication itself is
+/// done by `finalize`, which chooses between Straus' and Pippenger's
+/// algorithms from the final number of terms exactly as
+/// `EdwardsPoint::vartime_multiscalar_mul` does.
+///
+/// As with `vartime_multiscalar_mul`, the running time depends on
+/// the scalars, so they should be public.
+///
+/// # Example
+///
+/// ```
+/// use curve25519_dalek::constants;
+/// use curve25519_dalek::edwards::VartimeMultiscalarBuilder;
+/// use curve25519_dalek::scalar::Scalar;
+///
+/// let B = constants::ED25519_BASEPOINT_POINT;
+///
+/// let mut builder = VartimeMultiscalarBuilder::new();
+/// builder.push(Scalar::from(3u64), B);
+/// builder.push(Scalar::from(5u64), B + B);
+/// builder.push(-Scalar::from(13u64), B);
+///
+/// assert!(bool::from(builder.finalize_is_identity()));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct VartimeMultiscalarBuilder {
+    scalars: Vec<Scalar>,
+    points: Vec<EdwardsPoint>,
+}
+
+#[cfg(feature = "alloc")]
+impl VartimeMultiscalarBuilder {
+    /// Create a builder with no terms.
+    pub fn new() -> VartimeMultiscalarBuilder {
+        VartimeMultiscalarBuilder::default()
+    }
+
+    /// Create a builder with no terms, with room for `capacity` terms
+    /// before it reallocates.
+    pub fn with_capacity(capacity: usize) -> VartimeMultiscalarBuilder {
+        VartimeMultiscalarBuilder {
+            scalars: Vec::with_capacity(capacity),
+            points: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Add the term \\(cP\\).
+    pub fn push(&mut self, scalar: Scalar, point: EdwardsPoint) {
+        self.scalars.push(scalar);
+        self.points.push(point);
+    }
+
+    /// The number of terms added so far.
+    pub fn len(&self) -> usize {
+        self.scalars.len()
+    }
+
+    /// Returns `true` if no terms have been added.
+    pub fn is_empty(&self) -> bool {
+        self.scalars.is_empty()
+    }
+
+    /// Compute \\(c\_1 P\_1 + \cdots + c\_n P\_n\\) from the terms
+    /// added so far, in variable time.
+    ///
+    /// With no terms, this is the identity.
+    pub fn finalize(self) -> EdwardsPoint {
+        EdwardsPoint::vartime_multiscalar_mul(&self.scalars, &self.points)
+    }
+
+    /// Check whether \\(c\_1 P\_1 + \cdots + c\_n P\_n\\) is the
+    /// identity, as when checking a verification equation.
+    ///
+    /// The multiplication is computed in variable time, as by
+    /// `finalize`; only the final comparison is constant time.
+    pub fn finalize_is_identity(self) -> Choice {
+        self.finalize().ct_eq(&EdwardsPoint::identity())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Extend<(Scalar, EdwardsPoint)> for VartimeMultiscalarBuilder {
+    fn extend<T>(&mut self, terms: T)
+    where
+        T: IntoIterator<Item = (Scalar, EdwardsPoint)>,
+    {
+        for (scalar, point) in terms {
+            self.push(scalar, point);
+        }
+    }
+}
+
+/// Precomputation for variable-time multiscalar multiplication with `EdwardsPoint`s.
+// This wraps the inner implementation in a facade type so that we can
+// decouple stability of the inner type from the stability of the
+// outer type.
+#[cfg(feature = "alloc")]
+pub struct VartimeEdwardsPrecomputation(scalar_mul::precomputed_straus::VartimePrecomputedStraus);
+
+#[cfg(feature = "alloc")]
+impl VartimePrecomputedMultiscalarMul for VartimeEdwardsPrecomputation {
+    type Point = EdwardsPoint;
+
+    fn new<I>(static_points: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Self::Point>,
+    {
+        Self(scalar_mul::precomputed_straus::VartimePrecomputedStraus::new(static_points))
+    }
+
+    fn optional_mixed_multiscalar_mul<I, J, K>(
+        &self,
+        static_scalars: I,
+        dynamic_scalars: J,
+        dynamic_points: K,
+    ) -> Option<Self::Point>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator,
+        J::Item: Borrow<Scalar>,
+        K: IntoIterator<Item = Option<Self::Point>>,
+    {
+        self.0
+            .optional_mixed_multiscalar_mul(static_scalars, dynamic_scalars, dynamic_points)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VartimeEdwardsPrecomputation {
+    /// Encode this precomputation as bytes, so that it can be cached
+    /// and restored with `from_bytes` rather than rebuilt.
+    ///
+    /// The encoding takes 6KB per static point.  It is specific to
+    /// this version of `curve25519-dalek`, and is not intended as a
+    /// stable interchange format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    /// Restore a precomputation encoded by `to_bytes`.
+    ///
+    /// Returns `None` if `bytes` is not a valid encoding.  The
+    /// precomputed multiples of each static point are checked for
+    /// consistency, which is much cheaper than computing them.
+    pub fn from_bytes(bytes: &[u8]) -> Option<VartimeEdwardsPrecomputation> {
+        scalar_mul::precomputed_straus::VartimePrecomputedStraus::from_bytes(bytes)
+            .map(VartimeEdwardsPrecomputation)
+    }
+}
+
+impl EdwardsPoint {
+    /// Fixed-base scalar multiplication by the Ed25519 basepoint.
+    ///
+    /// This uses the precomputed `constants::ED25519_BASEPOINT_TABLE`,
+    /// so it is several times faster than multiplying
+    /// `constants::ED25519_BASEPOINT_POINT` by `scalar`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+    /// # use curve25519_dalek::edwards::EdwardsPoint;
+    /// # use curve25519_dalek::scalar::Scalar;
+    /// # fn main() {
+    /// let scalar = Scalar::from(8675309u64);
+    ///
+    /// assert_eq!(EdwardsPoint::mul_base(&scalar), &ED25519_BASEPOINT_TABLE * &scalar);
+    /// # }
+    /// ```
+    #[cfg(feature = "stage2_build")]
+    pub fn mul_base(scalar: &Scalar) -> EdwardsPoint {
+        use constants::ED25519_BASEPOINT_TABLE;
+
+        &ED25519_BASEPOINT_TABLE * scalar
+    }
+
+    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
+    pub fn vartime_double_scalar_mul_basepoint(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+    ) -> EdwardsPoint {
+        scalar_mul::vartime_double_base::mul(a, A, b)
+    }
+
+    /// Compute \\(aA + bB\\) in variable time, where \\(A\\) and
+    /// \\(B\\) are arbitrary points.
+    ///
+    /// This interleaves the two scalar multiplications, sharing the
+    /// doublings between them, so it is faster than computing
+    /// \\(aA\\) and \\(bB\\) separately.  When \\(B\\) is the
+    /// Ed25519 basepoint, `vartime_double_scalar_mul_basepoint` is
+    /// faster still, since it uses a precomputed table for \\(B\\).
+    pub fn vartime_double_scalar_mul(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        B: &EdwardsPoint,
+    ) -> EdwardsPoint {
+        scalar_mul::vartime_double_base::mul_arbitrary(a, A, b, B)
+    }
+}
+
+macro_rules! impl_basepoint_table {
+    (Name = $name:ident, LookupTable = $table:ident, Width = $w:expr, Tables = $tables:expr, Additions = $adds:expr) => {
+
+impl $name {
+    /// The computation uses Pippeneger's algorithm, as described on
+    /// page 13 of the Ed25519 paper.  Write the scalar \\(a\\) in radix \\(2\^w\\) with
+    /// coefficients in \\([-2\^{w-1}, 2\^{w-1})\\), i.e.,
+    /// $$
+    ///     a = a\_0 + a\_1 2\^{w} + \cdots + a\_{n-1} 2\^{w(n-1)},
+    /// $$
+    /// with \\(-2\^{w-1} \leq a_i < 2\^{w-1}\\), \\(-2\^{w-1} \leq a\_{n-1} \leq 2\^{w-1}\\).  Then
+    /// $$
+    ///     a B = a\_0 B + a\_1 2\^{w} B + \cdots + a\_{n-1} 2\^{w(n-1)} B.
+    /// $$
+    /// Grouping even and odd coefficients gives
     /// $$
     /// \begin{aligned}
-    ///     a B = \quad a\_0 16\^0 B +& a\_2 16\^2 B + \cdots + a\_{62} 16\^{62} B    \\\\
-    ///               + a\_1 16\^1 B +& a\_3 16\^3 B + \cdots + a\_{63} 16\^{63} B    \\\\
-    ///         = \quad(a\_0 16\^0 B +& a\_2 16\^2 B + \cdots + a\_{62} 16\^{62} B)   \\\\
-    ///            + 16(a\_1 16\^0 B +& a\_3 16\^2 B + \cdots + a\_{63} 16\^{62} B).  \\\\
+    ///     a B = \quad a\_0 2\^{0w} B +& a\_2 2\^{2w} B + \cdots + a\_{n-2} 2\^{w(n-2)} B    \\\\
+    ///               + a\_1 2\^{1w} B +& a\_3 2\^{3w} B + \cdots + a\_{n-1} 2\^{w(n-1)} B    \\\\
+    ///         = \quad(a\_0 2\^{0w} B +& a\_2 2\^{2w} B + \cdots + a\_{n-2} 2\^{w(n-2)} B)   \\\\
+    ///            + 2\^w(a\_1 2\^{0w} B +& a\_3 2\^{2w} B + \cdots + a\_{n-1} 2\^{w(n-2)} B).  \\\\
     /// \end{aligned}
     /// $$
-    /// For each \\(i = 0 \ldots 31\\), we create a lookup table of
+    /// For each \\(i = 0 \ldots \lceil n/2 \rceil - 1\\), we create a lookup table of
     /// $$
-    /// [16\^{2i} B, \ldots, 8\cdot16\^{2i} B],
+    /// [2\^{2wi} B, \ldots, 2\^{w-1}\cdot2\^{2wi} B],
     /// $$
-    /// and use it to select \\( x \cdot 16\^{2i} \cdot B \\) in constant time.
+    /// and use it to select \\( y \cdot 2\^{2wi} \cdot B \\) in constant time.
     ///
-    /// The radix-\\(16\\) representation requires that the scalar is bounded
+    /// The radix-\\(2\^w\\) representation requires that the scalar is bounded
     /// by \\(2\^{255}\\), which is always the case.
     fn basepoint_mul(&self, scalar: &Scalar) -> EdwardsPoint {
-        let a = scalar.to_radix_16();
+        let a = scalar.to_radix_2w($w);
 
         let tables = &self.0;
         let mut P = EdwardsPoint::identity();
 
-        for i in (0..64).filter(|x| x % 2 == 1) {
+        for i in (0..$adds).filter(|x| x % 2 == 1) {
             P = (&P + &tables[i/2].select(a[i])).to_extended();
         }
 
-        P = P.mul_by_pow_2(4);
+        P = P.mul_by_pow_2($w);
 
-        for i in (0..64).filter(|x| x % 2 == 0) {
+        for i in (0..$adds).filter(|x| x % 2 == 0) {
             P = (&P + &tables[i/2].select(a[i])).to_extended();
         }
 
@@ -794,7 +2697,7 @@ impl EdwardsBasepointTable {
     }
 }
 
-impl<'a, 'b> Mul<&'b Scalar> for &'a EdwardsBasepointTable {
+impl<'a, 'b> Mul<&'b Scalar> for &'a $name {
     type Output = EdwardsPoint;
 
     /// Construct an `EdwardsPoint` from a `Scalar` \\(a\\) by
@@ -803,37 +2706,328 @@ impl<'a, 'b> Mul<&'b Scalar> for &'a EdwardsBasepointTable {
         // delegate to a private function so that its documentation appears in internal docs
         self.basepoint_mul(scalar)
     }
-}
+}
+
+impl<'a, 'b> Mul<&'a $name> for &'b Scalar {
+    type Output = EdwardsPoint;
+
+    /// Construct an `EdwardsPoint` from a `Scalar` \\(a\\) by
+    /// computing the multiple \\(aB\\) of this basepoint \\(B\\).
+    fn mul(self, basepoint_table: &'a $name) -> EdwardsPoint {
+        basepoint_table * &self
+    }
+}
+
+impl $name {
+    /// Create a table of precomputed multiples of `basepoint`, which
+    /// may be any `EdwardsPoint`.
+    pub fn create(basepoint: &EdwardsPoint) -> $name {
+        // XXX use init_with
+        let mut table = $name([$table::default(); $tables]);
+        table.fill(basepoint);
+        table
+    }
+
+    /// Create a heap-allocated table of precomputed multiples of
+    /// `basepoint`, which may be any `EdwardsPoint`.
+    ///
+    /// The table is allocated directly on the heap and filled in
+    /// place, so it never transits the stack.  This makes it safe to
+    /// use on threads with small stacks, where `create` may overflow.
+    #[cfg(feature = "alloc")]
+    pub fn create_boxed(basepoint: &EdwardsPoint) -> Box<$name> {
+        #[cfg(not(feature = "std"))]
+        use alloc::alloc::{alloc_zeroed, handle_alloc_error, Layout};
+        #[cfg(feature = "std")]
+        use std::alloc::{alloc_zeroed, handle_alloc_error, Layout};
+
+        // `Box::new` would construct its argument on the stack before
+        // moving it to the heap, so allocate the memory ourselves.
+        let layout = Layout::new::<$name>();
+        // This is sound because the table consists only of integer
+        // limbs, so the all-zero bit pattern is a valid value, and the
+        // memory is allocated with the layout `Box` expects.
+        let mut table = unsafe {
+            let ptr = alloc_zeroed(layout) as *mut $name;
+            if ptr.is_null() {
+                handle_alloc_error(layout);
+            }
+            Box::from_raw(ptr)
+        };
+        table.fill(basepoint);
+        table
+    }
+
+    /// Fill this table with the precomputed multiples of `basepoint`.
+    fn fill(&mut self, basepoint: &EdwardsPoint) {
+        let mut P = *basepoint;
+        for i in 0..$tables {
+            // P = (2^{2w})^i * B
+            self.0[i] = $table::from(&P);
+            P = P.mul_by_pow_2($w + $w);
+        }
+    }
+
+    /// Compute \\(aG + bH\\), where \\(G\\) and \\(H\\) are the
+    /// basepoints of `table_g` and `table_h`, in constant time.
+    ///
+    /// This gives the same result as `&table_g * a + &table_h * b`.
+    /// The table selections for both scalars are interleaved so that
+    /// the doublings are shared; since a fixed-base multiplication
+    /// only needs \\(w\\) doublings, most of the cost is in the
+    /// additions, so the saving is modest.
+    pub fn double_mul(table_g: &$name, a: &Scalar, table_h: &$name, b: &Scalar) -> EdwardsPoint {
+        let a = a.to_radix_2w($w);
+        let b = b.to_radix_2w($w);
+
+        let mut P = EdwardsPoint::identity();
+
+        for i in (0..$adds).filter(|x| x % 2 == 1) {
+            P = (&P + &table_g.0[i/2].select(a[i])).to_extended();
+            P = (&P + &table_h.0[i/2].select(b[i])).to_extended();
+        }
+
+        P = P.mul_by_pow_2($w);
+
+        for i in (0..$adds).filter(|x| x % 2 == 0) {
+            P = (&P + &table_g.0[i/2].select(a[i])).to_extended();
+            P = (&P + &table_h.0[i/2].select(b[i])).to_extended();
+        }
+
+        P
+    }
+
+    /// Encode this table as bytes, so that it can be cached and
+    /// restored with `from_bytes_verified` rather than rebuilt.
+    ///
+    /// Each precomputed multiple takes 96 bytes.  The encoding is
+    /// specific to this version of `curve25519-dalek`, and is not
+    /// intended as a stable interchange format.
+    #[cfg(feature = "alloc")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity($tables * self.0[0].0.len() * 96);
+        for table in self.0.iter() {
+            for P in table.0.iter() {
+                bytes.extend_from_slice(&P.y_plus_x.to_bytes());
+                bytes.extend_from_slice(&P.y_minus_x.to_bytes());
+                bytes.extend_from_slice(&P.xy2d.to_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Restore a table encoded by `to_bytes`, checking that it is the
+    /// table for `expected_basepoint`.
+    ///
+    /// Every precomputed multiple is checked against
+    /// `expected_basepoint`, so a corrupted or maliciously modified
+    /// table is rejected rather than silently giving wrong results.
+    /// The checks are done in projective coordinates, which is much
+    /// cheaper than building the table with `create`, since that
+    /// needs an inversion per entry.
+    ///
+    /// # Return
+    ///
+    /// * `Some(table)` if `bytes` is the encoding of
+    ///   `create(expected_basepoint)`;
+    ///
+    /// * `None` otherwise.
+    pub fn from_bytes_verified(bytes: &[u8], expected_basepoint: &EdwardsPoint) -> Option<$name> {
+        let mut table = $name([$table::default(); $tables]);
+        let entry_count = table.0[0].0.len();
+        if bytes.len() != $tables * entry_count * 96 {
+            return None;
+        }
+
+        let two_inv = FieldElement::from_u64(2).invert();
+
+        // P = (2^{2w})^i * B, the basepoint of the i-th lookup table
+        let mut P = *expected_basepoint;
+        for (lookup_table, table_bytes) in table.0.iter_mut().zip(bytes.chunks(entry_count * 96)) {
+            // Q = (j+1) * P, the expected value of the j-th entry
+            let mut Q = P;
+            for (j, entry_bytes) in table_bytes.chunks(96).enumerate() {
+                let (entry, affine) = AffineNielsPoint::from_bytes(entry_bytes, &two_inv)?;
+                if Q.X != &affine.X * &Q.Z || Q.Y != &affine.Y * &Q.Z {
+                    return None;
+                }
+                lookup_table.0[j] = entry;
+                Q = (&affine + &lookup_table.0[0]).to_extended();
+            }
+            P = P.mul_by_pow_2($w + $w);
+        }
+
+        Some(table)
+    }
+
+    /// Get the basepoint for this table as an `EdwardsPoint`.
+    pub fn basepoint(&self) -> EdwardsPoint {
+        // self.0[0].select(1) = 1*(2^{2w})^0*B
+        // but as an `AffineNielsPoint`, so add identity to convert to extended.
+        (&EdwardsPoint::identity() + &self.0[0].select(1)).to_extended()
+    }
+}
+
+impl Debug for $name {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}([\n", stringify!($name))?;
+        for i in 0..$tables {
+            write!(f, "\t{:?},\n", &self.0[i])?;
+        }
+        write!(f, "])")
+    }
+}
+
+}} // End macro_rules! impl_basepoint_table
+
+/// A precomputed table of multiples of a basepoint, for accelerating
+/// fixed-base scalar multiplication.  One table, for the Ed25519
+/// basepoint, is provided in the `constants` module.
+///
+/// Despite the name, the "basepoint" can be any `EdwardsPoint`: a
+/// table created with `EdwardsBasepointTable::create(&P)` computes
+/// \\(aP\\) for any `Scalar` \\(a\\), several times faster than
+/// `&P * &a`.  This is worthwhile whenever the same point is
+/// multiplied by many different scalars.
+///
+/// This table uses radix \\(16\\), with \\(32\\) lookup tables of \\(8\\)
+/// points each.  The basepoint tables are reasonably large (30KB), so
+/// they should probably be boxed, for instance by using `create_boxed`.
+///
+/// Tables in other radices, trading size for speed or vice versa,
+/// are provided by `EdwardsBasepointTableRadix32`,
+/// `EdwardsBasepointTableRadix64`, `EdwardsBasepointTableRadix128`,
+/// and `EdwardsBasepointTableRadix256`.  A table of radix \\(2\^w\\)
+/// computes a multiple using \\(\lceil 256/w \rceil\\) mixed additions
+/// and \\(w\\) doublings, but each addition needs a constant-time scan
+/// of a lookup table of \\(2\^{w-1}\\) points:
+///
+/// | Type                            | Size   | Additions | Points scanned |
+/// |---------------------------------|--------|-----------|----------------|
+/// | `EdwardsBasepointTable`         | 30KB   | 64        | 512            |
+/// | `EdwardsBasepointTableRadix32`  | 49KB   | 52        | 832            |
+/// | `EdwardsBasepointTableRadix64`  | 83KB   | 43        | 1376           |
+/// | `EdwardsBasepointTableRadix128` | 143KB  | 37        | 2368           |
+/// | `EdwardsBasepointTableRadix256` | 255KB  | 33        | 4224           |
+///
+/// Which table is fastest depends on the relative cost of point
+/// additions and memory accesses on the target platform, so it's
+/// best to benchmark.
+#[derive(Clone)]
+pub struct EdwardsBasepointTable(pub(crate) [LookupTable<AffineNielsPoint>; 32]);
+
+/// A precomputed table of multiples of a basepoint, using radix
+/// \\(32\\), with \\(26\\) lookup tables of \\(16\\) points each (49KB).
+///
+/// See `EdwardsBasepointTable` for a comparison of the table sizes.
+#[derive(Clone)]
+pub struct EdwardsBasepointTableRadix32(pub(crate) [LookupTableRadix32<AffineNielsPoint>; 26]);
+
+/// A precomputed table of multiples of a basepoint, using radix
+/// \\(64\\), with \\(22\\) lookup tables of \\(32\\) points each (83KB).
+///
+/// See `EdwardsBasepointTable` for a comparison of the table sizes.
+#[derive(Clone)]
+pub struct EdwardsBasepointTableRadix64(pub(crate) [LookupTableRadix64<AffineNielsPoint>; 22]);
+
+/// A precomputed table of multiples of a basepoint, using radix
+/// \\(128\\), with \\(19\\) lookup tables of \\(64\\) points each (143KB).
+///
+/// See `EdwardsBasepointTable` for a comparison of the table sizes.
+#[derive(Clone)]
+pub struct EdwardsBasepointTableRadix128(pub(crate) [LookupTableRadix128<AffineNielsPoint>; 19]);
+
+/// A precomputed table of multiples of a basepoint, using radix
+/// \\(256\\), with \\(17\\) lookup tables of \\(128\\) points each (255KB).
+///
+/// See `EdwardsBasepointTable` for a comparison of the table sizes.
+#[derive(Clone)]
+pub struct EdwardsBasepointTableRadix256(pub(crate) [LookupTableRadix256<AffineNielsPoint>; 17]);
+
+impl_basepoint_table! {Name = EdwardsBasepointTable,         LookupTable = LookupTable,         Width = 4, Tables = 32, Additions = 64}
+impl_basepoint_table! {Name = EdwardsBasepointTableRadix32,  LookupTable = LookupTableRadix32,  Width = 5, Tables = 26, Additions = 52}
+impl_basepoint_table! {Name = EdwardsBasepointTableRadix64,  LookupTable = LookupTableRadix64,  Width = 6, Tables = 22, Additions = 43}
+impl_basepoint_table! {Name = EdwardsBasepointTableRadix128, LookupTable = LookupTableRadix128, Width = 7, Tables = 19, Additions = 37}
+impl_basepoint_table! {Name = EdwardsBasepointTableRadix256, LookupTable = LookupTableRadix256, Width = 8, Tables = 17, Additions = 33}
+
+/// A precomputed table of odd multiples of a single point, for
+/// variable-time scalar multiplication of a base which is reused
+/// with many different scalars.
+///
+/// This is the variable-time analogue of `EdwardsBasepointTable`:
+/// it stores the \\(64\\) odd multiples \\(A, 3A, \ldots, 127A\\)
+/// (10KB), and `mul` uses a width-\\(8\\) non-adjacent form of the
+/// scalar, so that each multiplication costs about \\(256\\)
+/// doublings and \\(28\\) additions, without rebuilding the table.
+///
+/// Since it runs in variable time, it should only be used with public
+/// scalars, such as challenges in a sigma protocol or when verifying.
+///
+/// # Example
+///
+/// ```
+/// # extern crate curve25519_dalek;
+/// # use curve25519_dalek::constants;
+/// # use curve25519_dalek::edwards::VartimeEdwardsPrecomputedBase;
+/// # use curve25519_dalek::scalar::Scalar;
+/// # fn main() {
+/// let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234u64);
+/// let A_table = VartimeEdwardsPrecomputedBase::new(&A);
+///
+/// for i in 1..4u64 {
+///     let c = Scalar::from(i).invert();
+///     assert_eq!(A_table.mul(&c), A * c);
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct VartimeEdwardsPrecomputedBase(NafLookupTable8<ProjectiveNielsPoint>);
+
+impl VartimeEdwardsPrecomputedBase {
+    /// Precompute the odd multiples of `A`.
+    pub fn new(A: &EdwardsPoint) -> VartimeEdwardsPrecomputedBase {
+        VartimeEdwardsPrecomputedBase(NafLookupTable8::<ProjectiveNielsPoint>::from(A))
+    }
 
-impl<'a, 'b> Mul<&'a EdwardsBasepointTable> for &'b Scalar {
-    type Output = EdwardsPoint;
+    /// Compute \\(sA\\) in variable time, where \\(A\\) is the
+    /// precomputed point.
+    pub fn mul(&self, scalar: &Scalar) -> EdwardsPoint {
+        let naf = scalar.non_adjacent_form(8);
+
+        // Find starting index
+        let mut i: usize = 255;
+        for j in (0..256).rev() {
+            i = j;
+            if naf[i] != 0 {
+                break;
+            }
+        }
 
-    /// Construct an `EdwardsPoint` from a `Scalar` \\(a\\) by
-    /// computing the multiple \\(aB\\) of this basepoint \\(B\\).
-    fn mul(self, basepoint_table: &'a EdwardsBasepointTable) -> EdwardsPoint {
-        basepoint_table * &self
-    }
-}
+        let mut r = ProjectivePoint::identity();
+        loop {
+            let mut t = r.double();
 
-impl EdwardsBasepointTable {
-    /// Create a table of precomputed multiples of `basepoint`.
-    pub fn create(basepoint: &EdwardsPoint) -> EdwardsBasepointTable {
-        // XXX use init_with
-        let mut table = EdwardsBasepointTable([LookupTable::default(); 32]);
-        let mut P = *basepoint;
-        for i in 0..32 {
-            // P = (16^2)^i * B
-            table.0[i] = LookupTable::from(&P);
-            P = P.mul_by_pow_2(8);
+            if naf[i] > 0 {
+                t = &t.to_extended() + &self.0.select(naf[i] as usize);
+            } else if naf[i] < 0 {
+                t = &t.to_extended() - &self.0.select(-naf[i] as usize);
+            }
+
+            r = t.to_projective();
+
+            if i == 0 {
+                break;
+            }
+            i -= 1;
         }
-        table
+
+        r.to_extended()
     }
+}
 
-    /// Get the basepoint for this table as an `EdwardsPoint`.
-    pub fn basepoint(&self) -> EdwardsPoint {
-        // self.0[0].select(1) = 1*(16^2)^0*B
-        // but as an `AffineNielsPoint`, so add identity to convert to extended.
-        (&EdwardsPoint::identity() + &self.0[0].select(1)).to_extended()
+impl Debug for VartimeEdwardsPrecomputedBase {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "VartimeEdwardsPrecomputedBase({:?})", &self.0)
     }
 }
 
@@ -843,8 +3037,139 @@ impl EdwardsPoint {
         self.mul_by_pow_2(3)
     }
 
+    /// Clear the torsion component of this point, returning a point in
+    /// the prime-order subgroup.
+    ///
+    /// Since the curve has cofactor \\(8\\), this computes \\([8]P\\),
+    /// exactly like `mul_by_cofactor`.  The result is always
+    /// torsion-free, but it is a different point than \\(P\\), even
+    /// when \\(P\\) is already torsion-free: writing
+    /// \\(P = P' + T\\) with \\(P'\\) in the prime-order subgroup and
+    /// \\(T\\) a torsion point, the result is \\([8]P'\\), not
+    /// \\(P'\\).  Protocols which clear cofactors must do so
+    /// consistently, for instance by also multiplying scalars by
+    /// \\(8\\), or by multiplying by \\(8\^{-1} \bmod \ell\\)
+    /// afterwards to recover \\(P'\\).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    ///
+    /// let P = constants::ED25519_BASEPOINT_POINT;
+    /// let T = constants::EIGHT_TORSION[1];
+    ///
+    /// // P + T is not torsion-free, but clearing the cofactor removes T
+    /// let Q = (P + T).clear_cofactor();
+    /// assert!(Q.is_torsion_free());
+    /// assert_eq!(Q, P.mul_by_cofactor());
+    /// ```
+    pub fn clear_cofactor(&self) -> EdwardsPoint {
+        self.mul_by_cofactor()
+    }
+
+    /// Compute \\([n]P\\) for a small public multiplier \\(n\\).
+    ///
+    /// This uses a plain double-and-add over the bits of \\(n\\),
+    /// so it costs one doubling per bit of \\(n\\) and one addition
+    /// per set bit, which is much cheaper than a full scalar
+    /// multiplication when \\(n\\) is small.
+    ///
+    /// # Warning
+    ///
+    /// This function is **not** constant-time with respect to \\(n\\):
+    /// its running time reveals the bit length and Hamming weight of
+    /// \\(n\\).  It must only be used with public multipliers.  It is
+    /// still constant-time with respect to \\(P\\).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let P = constants::ED25519_BASEPOINT_POINT;
+    ///
+    /// assert_eq!(P.mul_by_u64(3), P * Scalar::from(3u64));
+    /// assert_eq!(P.mul_by_u64(8), P.mul_by_cofactor());
+    /// ```
+    pub fn mul_by_u64(&self, n: u64) -> EdwardsPoint {
+        if n == 0 {
+            return EdwardsPoint::identity();
+        }
+
+        let P = self.to_projective_niels();
+        let mut r = self.to_projective();
+        // The top set bit of n is accounted for by starting at P.
+        for i in (0..(63 - n.leading_zeros())).rev() {
+            let mut t = r.double();
+            if (n >> i) & 1 == 1 {
+                t = &t.to_extended() + &P;
+            }
+            r = t.to_projective();
+        }
+        r.to_extended()
+    }
+
+    /// Compute \\([n]P\\), where \\(n\\) is the integer whose binary
+    /// digits are given by `bits`, most significant bit first.
+    ///
+    /// The integer \\(n\\) may have any length, and is not reduced
+    /// modulo the group order, so this can be used to multiply by
+    /// values (such as wide hash outputs) that are not `Scalar`s.
+    ///
+    /// # Warning
+    ///
+    /// This is a plain double-and-add, and is **not** constant-time
+    /// with respect to `bits`.  It must only be used with public
+    /// multipliers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let P = constants::ED25519_BASEPOINT_POINT;
+    ///
+    /// // 6 = 0b110
+    /// let bits = [true, true, false];
+    /// assert_eq!(P.mul_bits(bits.iter().cloned()), P * Scalar::from(6u64));
+    /// ```
+    pub fn mul_bits<I>(&self, bits: I) -> EdwardsPoint
+        where I: IntoIterator<Item = bool>
+    {
+        let P = self.to_projective_niels();
+        let mut r = ProjectivePoint::identity();
+        for bit in bits {
+            let mut t = r.double();
+            if bit {
+                t = &t.to_extended() + &P;
+            }
+            r = t.to_projective();
+        }
+        r.to_extended()
+    }
+
     /// Compute \\([2\^k] P \\) by successive doublings. Requires \\( k > 0 \\).
-    pub(crate) fn mul_by_pow_2(&self, k: u32) -> EdwardsPoint {
+    ///
+    /// The intermediate doublings stay in projective coordinates, so
+    /// this is faster than doubling an `EdwardsPoint` \\(k\\) times.
+    ///
+    /// The requirement \\( k > 0 \\) is only checked in debug builds;
+    /// with \\( k = 0 \\) a release build will not return \\(P\\).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let P = constants::ED25519_BASEPOINT_POINT;
+    ///
+    /// assert_eq!(P.mul_by_pow_2(5), P * Scalar::from(32u64));
+    /// ```
+    pub fn mul_by_pow_2(&self, k: u32) -> EdwardsPoint {
         debug_assert!( k > 0 );
         let mut r: CompletedPoint;
         let mut s = self.to_projective();
@@ -855,6 +3180,100 @@ impl EdwardsPoint {
         s.double().to_extended()
     }
 
+    /// Compute \\(sP\\) in constant time, using signed radix-\\(2\^w\\)
+    /// digits and a table of the \\(2\^{w-1}\\) multiples
+    /// \\([1]P, \ldots, [2\^{w-1}]P\\).
+    ///
+    /// The `*` operator uses \\(w = 4\\).  Larger windows need fewer
+    /// table lookups and additions, but each lookup scans a larger
+    /// table and the table takes longer to build, so they only pay
+    /// off when the table cost is amortized, or on platforms where
+    /// additions are relatively expensive.  The result is the same
+    /// for every \\(w\\).
+    ///
+    /// # Panics
+    ///
+    /// If `w` is not between 4 and 8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let P = constants::ED25519_BASEPOINT_POINT;
+    /// let s = Scalar::from(123456789u64);
+    ///
+    /// assert_eq!(P.mul_with_window(&s, 6), P * s);
+    /// ```
+    pub fn mul_with_window(&self, scalar: &Scalar, w: usize) -> EdwardsPoint {
+        scalar_mul::variable_base::mul_with_window(self, scalar, w)
+    }
+
+    /// Compute \\(kP\_i\\) for each of the `points` \\(P\_i\\) and the
+    /// same `scalar` \\(k\\), in constant time.
+    ///
+    /// This gives the same results as multiplying each point by the
+    /// scalar with `*`, but computes the signed-digit recoding of the
+    /// scalar only once.  Each point still needs its own table of
+    /// small multiples and its own doublings, since those depend on
+    /// the point, so the saving is small relative to the total.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// let points = [B, B + B];
+    /// let k = Scalar::from(7u64);
+    ///
+    /// assert_eq!(EdwardsPoint::mul_by_scalar_batch(&points, &k), vec![B * k, (B + B) * k]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn mul_by_scalar_batch(points: &[EdwardsPoint], scalar: &Scalar) -> Vec<EdwardsPoint> {
+        let scalar_digits = scalar.to_radix_16();
+        points
+            .iter()
+            .map(|P| scalar_mul::variable_base::VariableBaseTable::new(P).mul_radix_16(&scalar_digits))
+            .collect()
+    }
+
+    /// Compute \\([\ell] P\\), where \\(\ell\\) is the order of the
+    /// prime-order subgroup.
+    ///
+    /// Since \\(\ell = 2\^{252} + \delta\\) with \\(\delta < 2\^{125}\\),
+    /// its width-5 non-adjacent form has only about \\(22\\) nonzero
+    /// digits, so this costs \\(252\\) doublings and about \\(22\\)
+    /// additions, rather than the \\(64\\) additions and table
+    /// lookups of a generic constant-time scalar multiplication.
+    ///
+    /// The sequence of operations depends only on \\(\ell\\), which
+    /// is public, and the point formulas are complete, so this runs
+    /// in constant time with respect to \\(P\\).
+    fn mul_by_basepoint_order(&self) -> EdwardsPoint {
+        let naf = constants::BASEPOINT_ORDER.non_adjacent_form(5);
+        let table = NafLookupTable5::<ProjectiveNielsPoint>::from(self);
+
+        // The top nonzero digit of l is at 2^252.
+        let mut r = ProjectivePoint::identity();
+        for i in (0..253).rev() {
+            let mut t = r.double();
+
+            if naf[i] > 0 {
+                t = &t.to_extended() + &table.select(naf[i] as usize);
+            } else if naf[i] < 0 {
+                t = &t.to_extended() - &table.select(-naf[i] as usize);
+            }
+
+            r = t.to_projective();
+        }
+
+        r.to_extended()
+    }
+
     /// Determine if this point is of small order.
     ///
     /// # Return
@@ -882,6 +3301,39 @@ impl EdwardsPoint {
         self.mul_by_cofactor().is_identity()
     }
 
+    /// Determine if this point is of small order, in constant time.
+    ///
+    /// This is equivalent to `is_small_order`, but returns a `Choice`
+    /// for branch-free rejection, and is cheaper than multiplying by
+    /// the cofactor or comparing against each of the eight points in
+    /// `constants::EIGHT_TORSION`.
+    ///
+    /// The points of order \\(1\\) and \\(2\\) are those with
+    /// \\(x = 0\\), and the points of order \\(4\\) are those with
+    /// \\(y = 0\\).  Doubling a point gives a point with \\(y = 0\\)
+    /// exactly when \\(x\^2 + y\^2 = 0\\), so these are the points of
+    /// order \\(8\\).  Since each condition is homogeneous, a point is
+    /// of small order if and only if \\(XY(X\^2 + Y\^2) = 0\\) in
+    /// projective coordinates, which costs two squarings and two
+    /// multiplications.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    ///
+    /// let P = constants::ED25519_BASEPOINT_POINT;
+    /// let Q = constants::EIGHT_TORSION[1];
+    ///
+    /// assert!(!bool::from(P.ct_is_small_order()));
+    /// assert!(bool::from(Q.ct_is_small_order()));
+    /// ```
+    pub fn ct_is_small_order(&self) -> Choice {
+        let XY = &self.X * &self.Y;
+        let XX_plus_YY = &self.X.square() + &self.Y.square();
+        (&XY * &XX_plus_YY).is_zero()
+    }
+
     /// Determine if this point is “torsion-free”, i.e., is contained in
     /// the prime-order subgroup.
     ///
@@ -908,8 +3360,16 @@ impl EdwardsPoint {
     /// // P + Q is not torsion-free
     /// assert_eq!((P+Q).is_torsion_free(), false);
     /// ```
+    ///
+    /// # Implementation
+    ///
+    /// Checks whether \\([\ell] P\\) is the identity.  Since \\(\ell\\)
+    /// is a fixed public scalar, the multiplication uses its sparse
+    /// non-adjacent form, which needs far fewer additions than a
+    /// generic scalar multiplication while still running in constant
+    /// time with respect to `self`.
     pub fn is_torsion_free(&self) -> bool {
-        (self * &constants::BASEPOINT_ORDER).is_identity()
+        self.mul_by_basepoint_order().is_identity()
     }
 }
 
@@ -917,20 +3377,36 @@ impl EdwardsPoint {
 // Debug traits
 // ------------------------------------------------------------------------
 
+/// With the alternate flag (`{:#?}`), an `EdwardsPoint` is printed
+/// as its canonical affine coordinates \\((x, y)\\), as big-endian
+/// hex integers, for comparison against other implementations.
+/// Otherwise, the raw projective coordinates are printed.
 impl Debug for EdwardsPoint {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "EdwardsPoint{{\n\tX: {:?},\n\tY: {:?},\n\tZ: {:?},\n\tT: {:?}\n}}",
-               &self.X, &self.Y, &self.Z, &self.T)
+        if f.alternate() {
+            let recip = self.Z.invert();
+            let x = (&self.X * &recip).to_bytes();
+            let y = (&self.Y * &recip).to_bytes();
+
+            write!(f, "EdwardsPoint {{\n    x: 0x")?;
+            for b in x.iter().rev() {
+                write!(f, "{:02x}", b)?;
+            }
+            write!(f, ",\n    y: 0x")?;
+            for b in y.iter().rev() {
+                write!(f, "{:02x}", b)?;
+            }
+            write!(f, ",\n}}")
+        } else {
+            write!(f, "EdwardsPoint{{\n\tX: {:?},\n\tY: {:?},\n\tZ: {:?},\n\tT: {:?}\n}}",
+                   &self.X, &self.Y, &self.Z, &self.T)
+        }
     }
 }
 
-impl Debug for EdwardsBasepointTable {
+impl Debug for EdwardsCachedPoint {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "EdwardsBasepointTable([\n")?;
-        for i in 0..32 {
-            write!(f, "\t{:?},\n", &self.0[i])?;
-        }
-        write!(f, "])")
+        write!(f, "EdwardsCachedPoint({:?})", self.0)
     }
 }
 
@@ -1001,15 +3477,433 @@ mod test {
         0xc0, 0x46, 0x83, 0x43, 0xde, 0x70, 0x4b, 0x85,
         0x09, 0x6f, 0xfe, 0x35, 0x4f, 0x13, 0x2b, 0x42]);
 
-    /// Test round-trip decompression for the basepoint.
-    #[test]
-    fn basepoint_decompression_compression() {
-        let base_X = FieldElement::from_bytes(&BASE_X_COORD_BYTES);
-        let bp = constants::ED25519_BASEPOINT_COMPRESSED.decompress().unwrap();
-        assert!(bp.is_valid());
-        // Check that decompression actually gives the correct X coordinate
-        assert_eq!(base_X, bp.X);
-        assert_eq!(bp.compress(), constants::ED25519_BASEPOINT_COMPRESSED);
+    /// Test round-trip decompression for the basepoint.
+    #[test]
+    fn basepoint_decompression_compression() {
+        let base_X = FieldElement::from_bytes(&BASE_X_COORD_BYTES);
+        let bp = constants::ED25519_BASEPOINT_COMPRESSED.decompress().unwrap();
+        assert!(bp.is_valid());
+        // Check that decompression actually gives the correct X coordinate
+        assert_eq!(base_X, bp.X);
+        assert_eq!(bp.compress(), constants::ED25519_BASEPOINT_COMPRESSED);
+    }
+
+    /// Test that `try_from_slice` rejects slices of the wrong length
+    #[test]
+    fn compressed_try_from_slice() {
+        let bytes = constants::ED25519_BASEPOINT_COMPRESSED.as_bytes();
+
+        let compressed = CompressedEdwardsY::try_from_slice(&bytes[..]).unwrap();
+        assert_eq!(compressed, constants::ED25519_BASEPOINT_COMPRESSED);
+        assert_eq!(CompressedEdwardsY::try_from(&bytes[..]).unwrap(), compressed);
+
+        assert!(CompressedEdwardsY::try_from_slice(&bytes[..31]).is_err());
+        assert!(CompressedEdwardsY::try_from_slice(&[0u8; 33]).is_err());
+        assert!(CompressedEdwardsY::try_from_slice(&[]).is_err());
+    }
+
+    #[test]
+    fn compressed_negate() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        for i in 0..8 {
+            let P = B * Scalar::from(i as u64 + 1) + constants::EIGHT_TORSION[i];
+            assert_eq!(P.compress().negate(), (-P).compress());
+            assert_eq!(P.compress().negate().negate(), P.compress());
+        }
+
+        // Points with x = 0 are their own negations
+        for T in [EdwardsPoint::identity(), constants::EIGHT_TORSION[4]].iter() {
+            assert_eq!(T.compress().negate(), T.compress());
+            assert_eq!(T.compress().negate(), (-T).compress());
+        }
+    }
+
+    #[test]
+    fn compressed_from_y_and_sign() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        for i in 0..8 {
+            let P = B * Scalar::from(i as u64 + 1) + constants::EIGHT_TORSION[i];
+            let (x, y) = P.to_affine();
+            let compressed = CompressedEdwardsY::from_y_and_sign(&y, x.is_negative());
+            assert_eq!(compressed, P.compress());
+            assert_eq!(CompressedEdwardsY::from_y_and_sign(&y, !x.is_negative()), compressed.negate());
+        }
+
+        // y is reduced before encoding
+        let y = FieldElement::from_bytes(&[0xff; 32]);
+        let encoded = CompressedEdwardsY::from_y_and_sign(&y, Choice::from(0));
+        assert_eq!(encoded.as_bytes()[0], 18);
+        assert_eq!(encoded.as_bytes()[31], 0);
+    }
+
+    #[test]
+    fn compressed_from_hex() {
+        let B = constants::ED25519_BASEPOINT_COMPRESSED;
+        let lower = "5866666666666666666666666666666666666666666666666666666666666666";
+        assert_eq!(CompressedEdwardsY::from_hex(lower), Ok(B));
+
+        // Digits may be in either case
+        let P = (constants::ED25519_BASEPOINT_POINT * Scalar::from(7u64)).compress();
+        let upper: ::std::string::String = P.as_bytes().iter().map(|b| format!("{:02X}", b)).collect();
+        assert_eq!(CompressedEdwardsY::from_hex(&upper), Ok(P));
+
+        assert_eq!(CompressedEdwardsY::from_hex(""), Err(FromHexError::InvalidLength));
+        assert_eq!(CompressedEdwardsY::from_hex(&lower[..63]), Err(FromHexError::InvalidLength));
+        assert_eq!(CompressedEdwardsY::from_hex(&format!("{}0", lower)), Err(FromHexError::InvalidLength));
+        assert_eq!(CompressedEdwardsY::from_hex(&lower.replace("58", "5g")), Err(FromHexError::InvalidDigit));
+        assert_eq!(CompressedEdwardsY::from_hex(&lower.replace("58", "0x")), Err(FromHexError::InvalidDigit));
+    }
+
+    /// Test that non-canonical encodings are detected
+    #[test]
+    fn compressed_is_canonical() {
+        assert!(constants::ED25519_BASEPOINT_COMPRESSED.is_canonical());
+        assert!(CompressedEdwardsY::identity().is_canonical());
+        assert!(constants::ED25519_BASEPOINT_POINT.neg().compress().is_canonical());
+
+        // y = p + 1 is an unreduced encoding of the identity
+        let mut p_plus_one = [0xffu8; 32];
+        p_plus_one[0] = 0xee;
+        p_plus_one[31] = 0x7f;
+        let non_canonical_identity = CompressedEdwardsY(p_plus_one);
+        assert_eq!(non_canonical_identity.decompress().unwrap(), EdwardsPoint::identity());
+        assert!(!non_canonical_identity.is_canonical());
+
+        // x = 0 with the sign bit set is another encoding of the identity
+        let mut negative_zero = [0u8; 32];
+        negative_zero[0] = 1;
+        negative_zero[31] = 0x80;
+        let non_canonical_identity = CompressedEdwardsY(negative_zero);
+        assert_eq!(non_canonical_identity.decompress().unwrap(), EdwardsPoint::identity());
+        assert!(!non_canonical_identity.is_canonical());
+    }
+
+    /// Test that strict decompression rejects non-canonical and torsion points
+    #[test]
+    fn compressed_decompress_strict() {
+        let B = constants::ED25519_BASEPOINT_COMPRESSED.decompress_strict().unwrap();
+        assert_eq!(B, constants::ED25519_BASEPOINT_POINT);
+        assert_eq!(
+            CompressedEdwardsY::identity().decompress_strict(),
+            Some(EdwardsPoint::identity())
+        );
+
+        // A non-canonical encoding of the identity
+        let mut negative_zero = [0u8; 32];
+        negative_zero[0] = 1;
+        negative_zero[31] = 0x80;
+        assert!(CompressedEdwardsY(negative_zero).decompress().is_some());
+        assert!(CompressedEdwardsY(negative_zero).decompress_strict().is_none());
+
+        // Points with a torsion component
+        for T in constants::EIGHT_TORSION.iter().skip(1) {
+            assert!(T.compress().decompress_strict().is_none());
+            assert!((B + T).compress().decompress_strict().is_none());
+        }
+    }
+
+    #[test]
+    fn batch_decompress_matches_decompress() {
+        use rand_core::RngCore;
+        let mut rng = rand::thread_rng();
+
+        // Random bytes decompress about half of the time; include
+        // enough inputs to take the parallel path when it is enabled.
+        let compressed: Vec<CompressedEdwardsY> = (0..3000)
+            .map(|_| {
+                let mut bytes = [0u8; 32];
+                rng.fill_bytes(&mut bytes);
+                CompressedEdwardsY(bytes)
+            })
+            .collect();
+
+        let expected: Vec<Option<EdwardsPoint>> = compressed.iter().map(|c| c.decompress()).collect();
+        assert!(expected.iter().any(|P| P.is_some()));
+        assert!(expected.iter().any(|P| P.is_none()));
+
+        assert_eq!(CompressedEdwardsY::batch_decompress(&compressed), expected);
+        assert_eq!(CompressedEdwardsY::batch_decompress(&compressed[..10]), &expected[..10]);
+        assert!(CompressedEdwardsY::batch_decompress(&[]).is_empty());
+
+        // Force a multi-threaded pool so the parallel path is taken
+        #[cfg(feature = "parallel")]
+        {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+            assert_eq!(pool.install(|| CompressedEdwardsY::batch_decompress(&compressed)), expected);
+        }
+    }
+
+    /// Test that the `Result` forms report the failing check
+    #[test]
+    fn compressed_decompress_result_errors() {
+        let B = constants::ED25519_BASEPOINT_COMPRESSED;
+        assert_eq!(B.decompress_result(), Ok(constants::ED25519_BASEPOINT_POINT));
+        assert_eq!(B.decompress_strict_result(), Ok(constants::ED25519_BASEPOINT_POINT));
+
+        // y = 2 is not on the curve
+        let mut two = [0u8; 32];
+        two[0] = 2;
+        let not_on_curve = CompressedEdwardsY(two);
+        assert_eq!(not_on_curve.decompress(), None);
+        assert_eq!(not_on_curve.decompress_result(), Err(DecompressionError::NotOnCurve));
+        assert_eq!(not_on_curve.decompress_strict_result(), Err(DecompressionError::NotOnCurve));
+
+        // A non-canonical encoding of the identity
+        let mut negative_zero = [0u8; 32];
+        negative_zero[0] = 1;
+        negative_zero[31] = 0x80;
+        let non_canonical = CompressedEdwardsY(negative_zero);
+        assert_eq!(non_canonical.decompress_result(), Ok(EdwardsPoint::identity()));
+        assert_eq!(non_canonical.decompress_strict_result(), Err(DecompressionError::NonCanonical));
+
+        // A canonical encoding of a point with a torsion component
+        let T = constants::EIGHT_TORSION[1].compress();
+        assert!(T.decompress_result().is_ok());
+        assert_eq!(T.decompress_strict_result(), Err(DecompressionError::HasTorsion));
+    }
+
+    #[test]
+    fn conditional_decompress() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let a = B.compress();
+        let b = (B * Scalar::from(5u64)).compress();
+        // y = 2 is not on the curve
+        let mut two = [0u8; 32];
+        two[0] = 2;
+        let invalid = CompressedEdwardsY(two);
+
+        let P = CompressedEdwardsY::conditional_decompress(&a, &b, Choice::from(0));
+        assert_eq!(P.unwrap(), B);
+        let P = CompressedEdwardsY::conditional_decompress(&a, &b, Choice::from(1));
+        assert_eq!(P.unwrap().compress(), b);
+
+        let P = CompressedEdwardsY::conditional_decompress(&a, &invalid, Choice::from(0));
+        assert!(bool::from(P.is_some()));
+        let P = CompressedEdwardsY::conditional_decompress(&a, &invalid, Choice::from(1));
+        assert!(bool::from(P.is_none()));
+    }
+
+    #[test]
+    fn decompress_with_montgomery_matches_to_montgomery() {
+        let mut rng = rand::thread_rng();
+        let mut encodings: Vec<CompressedEdwardsY> = constants::EIGHT_TORSION
+            .iter()
+            .map(|T| T.compress())
+            .collect();
+        encodings.extend((0..8).map(|_| {
+            (constants::ED25519_BASEPOINT_POINT * Scalar::random(&mut rng)).compress()
+        }));
+        // Non-canonical encodings of the points with y = 1 and y = -1
+        let mut y_one = [0u8; 32];
+        y_one[0] = 1;
+        y_one[31] = 0x80;
+        let mut y_minus_one = [0xffu8; 32];
+        y_minus_one[0] = 0xec;
+        encodings.push(CompressedEdwardsY(y_one));
+        encodings.push(CompressedEdwardsY(y_minus_one));
+
+        for compressed in encodings.iter() {
+            let P = compressed.decompress().unwrap();
+            let (Q, u) = compressed.decompress_with_montgomery().unwrap();
+            assert_eq!(P, Q);
+            assert_eq!(P.compress(), Q.compress());
+            assert_eq!(u, P.to_montgomery());
+        }
+
+        // y = 2 is not on the curve
+        let mut two = [0u8; 32];
+        two[0] = 2;
+        assert!(CompressedEdwardsY(two).decompress_with_montgomery().is_none());
+    }
+
+    #[test]
+    fn from_uniform_bytes_matches_elligator_encode() {
+        use rand_core::RngCore;
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+
+            let P = EdwardsPoint::from_uniform_bytes(&bytes);
+            assert!(P.is_valid());
+
+            let r_0 = FieldElement::from_bytes(&bytes);
+            assert_eq!(P.to_montgomery(), MontgomeryPoint::elligator_encode(&r_0));
+            assert_eq!(P.compress().as_bytes()[31] >> 7, bytes[31] >> 7);
+        }
+    }
+
+    #[test]
+    fn from_uniform_bytes_rfc9380_vectors() {
+        use hex;
+
+        // Test vectors from RFC 9380, Appendix J.5.2
+        // (edwards25519_XMD:SHA-512_ELL2_NU_), as (msg, u[0], P.y),
+        // with field elements written big-endian as in the RFC.
+        //
+        // The suite's Elligator 2 step is `elligator_encode`, and P is
+        // the cofactor-cleared Edwards lift of its output.  The RFC's
+        // birational map scales x by sqrt(-486664), which can change
+        // the sign of x, but not y, so only P.y is compared.
+        let vectors = [
+            ("",
+             "7f3e7fb9428103ad7f52db32f9df32505d7b427d894c5093f7a0f0374a30641d",
+             "222e314d04a4d5725e9f2aff9fb2a6b69ef375a1214eb19021ceab2d687f0f9b"),
+            ("abc",
+             "09cfa30ad79bd59456594a0f5d3a76f6b71c6787b04de98be5cd201a556e253b",
+             "67732d50f9a26f73111dd1ed5dba225614e538599db58ba30aaea1f5c827fa42"),
+            ("abcdef0123456789",
+             "475ccff99225ef90d78cc9338e9f6a6bb7b17607c0c4428937de75d33edba941",
+             "2f8a6c24dd1adde73909cada6a4a137577b0f179d336685c4a955a0a8e1a86fb"),
+            ("q128_qqq...",
+             "049a1c8bd51bcb2aec339f387d1ff51428b88d0763a91bcdf6929814ac95d03d",
+             "2af6ff6ef5ebba128b0774f4296cb4c2279a074658b083b8dcca91f57a603450"),
+            ("a512_aaa...",
+             "3cb0178a8137cefa5b79a3a57c858d7eeeaa787b2781be4a362a2f0750d24fa0",
+             "2c90c3d39eb18ff291d33441b35f3262cdd307162cc97c31bfcc7a4245891a37"),
+        ];
+
+        for &(msg, u, y) in vectors.iter() {
+            let mut u = hex::decode(u).unwrap();
+            let mut y = hex::decode(y).unwrap();
+            u.reverse();
+            y.reverse();
+
+            let P = EdwardsPoint::from_uniform_bytes(&u).mul_by_cofactor();
+            let mut P_y = P.compress().to_bytes();
+            P_y[31] &= 0x7f;
+            assert_eq!(P_y, y, "msg = {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn random_points() {
+        let mut rng = rand::thread_rng();
+
+        // Random points should be on the curve, and most should have a
+        // torsion component.
+        let points: Vec<EdwardsPoint> = (0..32).map(|_| EdwardsPoint::random(&mut rng)).collect();
+        assert!(points.iter().all(|P| P.is_valid()));
+        assert!(points.iter().any(|P| !P.is_torsion_free()));
+        assert!(points[0] != points[1]);
+
+        for _ in 0..8 {
+            let P = EdwardsPoint::random_prime_order(&mut rng);
+            assert!(P.is_valid());
+            assert!(P.is_torsion_free());
+            assert!(!P.is_identity());
+        }
+    }
+
+    #[test]
+    fn vartime_precomputed_base_vs_mul() {
+        let mut rng = rand::thread_rng();
+        let A = EdwardsPoint::random(&mut rng);
+        let A_table = VartimeEdwardsPrecomputedBase::new(&A);
+
+        for _ in 0..16 {
+            let s = Scalar::random(&mut rng);
+            assert_eq!(A_table.mul(&s), A * s);
+        }
+
+        assert_eq!(A_table.mul(&Scalar::zero()), EdwardsPoint::identity());
+        assert_eq!(A_table.mul(&Scalar::one()), A);
+        assert_eq!(A_table.mul(&A_SCALAR), A * A_SCALAR);
+    }
+
+    #[test]
+    fn mul_by_basepoint_order_vs_mul() {
+        let mut rng = rand::thread_rng();
+        for T in constants::EIGHT_TORSION.iter() {
+            let P = EdwardsPoint::random(&mut rng) + T;
+            assert_eq!(P.mul_by_basepoint_order(), P * constants::BASEPOINT_ORDER);
+        }
+
+        let B = constants::ED25519_BASEPOINT_POINT;
+        assert!(B.mul_by_basepoint_order().is_identity());
+        assert_eq!(
+            (B + constants::EIGHT_TORSION[1]).mul_by_basepoint_order(),
+            constants::EIGHT_TORSION[1] * constants::BASEPOINT_ORDER
+        );
+    }
+
+    #[test]
+    fn clear_cofactor_removes_torsion() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let eight_inv = Scalar::from(8u64).invert();
+        for T in constants::EIGHT_TORSION.iter() {
+            let Q = (B + T).clear_cofactor();
+            assert!(Q.is_torsion_free());
+            assert_eq!(Q, B.mul_by_cofactor());
+            // Multiplying by 1/8 mod l recovers the prime-order component
+            assert_eq!(Q * eight_inv, B);
+            assert!(T.clear_cofactor().is_identity());
+        }
+    }
+
+    #[test]
+    fn mul_bits_vs_scalar_mul() {
+        let P = constants::ED25519_BASEPOINT_POINT + constants::EIGHT_TORSION[5];
+
+        // The empty bit string is zero
+        assert!(P.mul_bits(iter::empty()).is_identity());
+
+        // Bits of a scalar, most significant first
+        let s = Scalar::from(1234567u64).invert();
+        assert_eq!(P.mul_bits(s.bits().rev()), P * s);
+
+        // A 512-bit multiplier, which is not reduced first
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let wide_bytes = [0xa5u8; 64];
+        let wide_bits = (0..512).rev().map(|i| (wide_bytes[i / 8] >> (i % 8)) & 1 == 1);
+        assert_eq!(B.mul_bits(wide_bits), B * Scalar::from_bytes_mod_order_wide(&wide_bytes));
+    }
+
+    #[test]
+    fn compressed_edwards_y_as_map_key() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let B = constants::ED25519_BASEPOINT_COMPRESSED;
+        let points = [B, BASE2_CMPRSSD, BASE16_CMPRSSD, B];
+
+        let hashed: HashSet<CompressedEdwardsY> = points.iter().cloned().collect();
+        assert_eq!(hashed.len(), 3);
+
+        // Ordering is lexicographic on the bytes
+        let ordered: Vec<CompressedEdwardsY> =
+            points.iter().cloned().collect::<BTreeSet<_>>().into_iter().collect();
+        assert_eq!(ordered, vec![B, BASE2_CMPRSSD, BASE16_CMPRSSD]);
+        assert!(CompressedEdwardsY([0u8; 32]) < CompressedEdwardsY([1u8; 32]));
+    }
+
+    #[test]
+    fn mul_by_u64_vs_scalar_mul() {
+        let P = constants::ED25519_BASEPOINT_POINT + constants::EIGHT_TORSION[3];
+        for n in [0u64, 1, 2, 3, 8, 255, 256, 0xdead_beef, 0xffff_ffff_ffff_ffff].iter() {
+            assert_eq!(P.mul_by_u64(*n), P * Scalar::from(*n));
+        }
+    }
+
+    #[test]
+    fn check_linear_combination() {
+        let mut rng = rand::thread_rng();
+        let scalars: Vec<Scalar> = (0..16).map(|_| Scalar::random(&mut rng)).collect();
+        let points: Vec<EdwardsPoint> = (0..16).map(|_| EdwardsPoint::random(&mut rng)).collect();
+        let T: EdwardsPoint = scalars.iter().zip(points.iter()).map(|(c, P)| c * P).sum();
+
+        assert_eq!(EdwardsPoint::check_linear_combination(&scalars, &points, &T).unwrap_u8(), 1);
+        assert!(EdwardsPoint::vartime_check_linear_combination(&scalars, &points, &T));
+
+        // A different target, including one differing by a torsion point
+        for U in [T + constants::ED25519_BASEPOINT_POINT, T + constants::EIGHT_TORSION[4]].iter() {
+            assert_eq!(EdwardsPoint::check_linear_combination(&scalars, &points, U).unwrap_u8(), 0);
+            assert!(!EdwardsPoint::vartime_check_linear_combination(&scalars, &points, U));
+        }
+
+        // The empty combination is the identity
+        let none: [Scalar; 0] = [];
+        let no_points: [EdwardsPoint; 0] = [];
+        assert!(EdwardsPoint::vartime_check_linear_combination(&none, &no_points, &EdwardsPoint::identity()));
     }
 
     /// Test sign handling in decompression
@@ -1120,6 +4014,490 @@ mod test {
         assert_eq!(aB_1.compress(), aB_2.compress());
     }
 
+    /// Test that a table for an arbitrary point agrees with variable-base
+    /// scalar multiplication
+    #[test]
+    fn basepoint_table_for_arbitrary_point() {
+        let mut rng = rand::thread_rng();
+        let P = constants::ED25519_BASEPOINT_POINT * Scalar::random(&mut rng);
+
+        let table = EdwardsBasepointTable::create(&P);
+        let boxed_table = EdwardsBasepointTable::create_boxed(&P);
+        assert_eq!(table.basepoint(), P);
+        assert_eq!(boxed_table.basepoint(), P);
+
+        for _ in 0..10 {
+            let s = Scalar::random(&mut rng);
+            assert_eq!(&table * &s, P * s);
+            assert_eq!(&*boxed_table * &s, P * s);
+        }
+    }
+
+    #[test]
+    fn create_boxed_on_small_stack() {
+        // The table is about 30KB, so building it on this stack would
+        // overflow.
+        let handle = ::std::thread::Builder::new()
+            .stack_size(16 * 1024)
+            .spawn(|| {
+                let B = EdwardsBasepointTable::create_boxed(&constants::ED25519_BASEPOINT_POINT);
+                B.basepoint().compress()
+            })
+            .unwrap();
+        assert_eq!(handle.join().unwrap(), constants::ED25519_BASEPOINT_COMPRESSED);
+    }
+
+    macro_rules! test_basepoint_table {
+        ($name:ident, $table:ident) => {
+            #[test]
+            fn $name() {
+                let mut rng = rand::thread_rng();
+                let P = constants::ED25519_BASEPOINT_POINT * Scalar::random(&mut rng);
+
+                let table = $table::create_boxed(&P);
+                assert_eq!(table.basepoint(), P);
+
+                let B = $table::create_boxed(&constants::ED25519_BASEPOINT_POINT);
+
+                let mut scalars = vec![Scalar::zero(), Scalar::one(), -Scalar::one()];
+                scalars.extend((0..10).map(|_| Scalar::random(&mut rng)));
+                for s in scalars.iter() {
+                    assert_eq!(&*table * s, P * s);
+                    assert_eq!(s * &*B, &constants::ED25519_BASEPOINT_TABLE * s);
+                }
+            }
+        };
+    }
+
+    test_basepoint_table!(basepoint_table_radix32, EdwardsBasepointTableRadix32);
+    test_basepoint_table!(basepoint_table_radix64, EdwardsBasepointTableRadix64);
+    test_basepoint_table!(basepoint_table_radix128, EdwardsBasepointTableRadix128);
+    test_basepoint_table!(basepoint_table_radix256, EdwardsBasepointTableRadix256);
+
+    #[test]
+    fn vartime_multiscalar_mul_compressed_matches_decompressed() {
+        let mut rng = rand::thread_rng();
+        for &n in &[0usize, 1, 16, 200] {
+            let scalars: Vec<Scalar> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+            let points: Vec<EdwardsPoint> = (0..n)
+                .map(|_| &constants::ED25519_BASEPOINT_TABLE * &Scalar::random(&mut rng))
+                .collect();
+            let compressed: Vec<CompressedEdwardsY> = points.iter().map(|P| P.compress()).collect();
+
+            let expected = EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+            let result = EdwardsPoint::vartime_multiscalar_mul_compressed(&scalars, &compressed);
+            assert_eq!(result, Some(expected));
+
+            if n > 0 {
+                // y = 2 is not the y-coordinate of a curve point
+                let mut bytes = [0u8; 32];
+                bytes[0] = 2;
+                let mut invalid = compressed.clone();
+                invalid[n / 2] = CompressedEdwardsY(bytes);
+                assert!(EdwardsPoint::vartime_multiscalar_mul_compressed(&scalars, &invalid).is_none());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn par_pippenger_matches_serial() {
+        use traits::VartimeMultiscalarMul;
+
+        let mut rng = rand::thread_rng();
+        let n = 600;
+        let scalars: Vec<Scalar> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+        let points: Vec<EdwardsPoint> = (0..n)
+            .map(|_| &constants::ED25519_BASEPOINT_TABLE * &Scalar::random(&mut rng))
+            .collect();
+
+        let expected = scalar_mul::pippenger::Pippenger::vartime_multiscalar_mul(&scalars, &points);
+        for &chunk_size in &[200, 256, 600, 1000] {
+            assert_eq!(par_pippenger(&scalars, &points, chunk_size), expected);
+        }
+
+        // The parallel dispatch still returns `None` for invalid points
+        let n = PARALLEL_PIPPENGER_THRESHOLD;
+        let scalars = vec![Scalar::one(); n];
+        let mut points = vec![Some(constants::ED25519_BASEPOINT_POINT); n];
+        points[n / 2] = None;
+        assert!(EdwardsPoint::optional_multiscalar_mul(&scalars, points).is_none());
+    }
+
+    #[test]
+    fn vartime_multiscalar_mul_with_params_matches_default() {
+        let mut rng = rand::thread_rng();
+        let n = 64;
+        let scalars: Vec<Scalar> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+        let points: Vec<EdwardsPoint> = (0..n)
+            .map(|_| &constants::ED25519_BASEPOINT_TABLE * &Scalar::random(&mut rng))
+            .collect();
+
+        let expected = EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+        for &algorithm in &[
+            MultiscalarMulAlgorithm::default(),
+            MultiscalarMulAlgorithm::Straus,
+            MultiscalarMulAlgorithm::Pippenger,
+            MultiscalarMulAlgorithm::Threshold(0),
+            MultiscalarMulAlgorithm::Threshold(n + 1),
+            MultiscalarMulAlgorithm::PippengerWithWindow(4),
+            MultiscalarMulAlgorithm::PippengerWithWindow(5),
+            MultiscalarMulAlgorithm::PippengerWithWindow(6),
+            MultiscalarMulAlgorithm::PippengerWithWindow(7),
+            MultiscalarMulAlgorithm::PippengerWithWindow(8),
+        ] {
+            let result = EdwardsPoint::vartime_multiscalar_mul_with_params(algorithm, &scalars, &points);
+            assert_eq!(result, expected);
+        }
+
+        assert_eq!(MultiscalarMulAlgorithm::default(), MultiscalarMulAlgorithm::Threshold(PIPPENGER_THRESHOLD));
+        assert!(!MultiscalarMulAlgorithm::default().use_pippenger(PIPPENGER_THRESHOLD - 1));
+        assert!(MultiscalarMulAlgorithm::default().use_pippenger(PIPPENGER_THRESHOLD));
+    }
+
+    #[test]
+    #[should_panic]
+    fn vartime_multiscalar_mul_with_invalid_window_panics() {
+        let scalars = [Scalar::one()];
+        let points = [constants::ED25519_BASEPOINT_POINT];
+        EdwardsPoint::vartime_multiscalar_mul_with_params(
+            MultiscalarMulAlgorithm::PippengerWithWindow(9),
+            &scalars,
+            &points,
+        );
+    }
+
+    #[test]
+    fn multiscalar_builder_vs_vartime_multiscalar_mul() {
+        let mut rng = rand::thread_rng();
+        let B = constants::ED25519_BASEPOINT_POINT;
+
+        assert!(VartimeMultiscalarBuilder::new().finalize().is_identity());
+
+        // Sizes on either side of the Straus/Pippenger crossover
+        for &n in [1usize, 16, PIPPENGER_THRESHOLD + 10].iter() {
+            let scalars: Vec<Scalar> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+            let points: Vec<EdwardsPoint> = (0..n).map(|_| B * Scalar::random(&mut rng)).collect();
+            let expected = EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+
+            let mut builder = VartimeMultiscalarBuilder::with_capacity(n);
+            for (s, P) in scalars.iter().zip(points.iter()) {
+                builder.push(*s, *P);
+            }
+            assert_eq!(builder.len(), n);
+            assert!(!bool::from(builder.clone().finalize_is_identity()));
+            assert_eq!(builder.clone().finalize(), expected);
+
+            // Cancelling the sum gives the identity
+            builder.extend(iter::once((Scalar::one(), -expected)));
+            assert!(bool::from(builder.finalize_is_identity()));
+        }
+    }
+
+    #[test]
+    fn vartime_multiscalar_mul_with_scalar_sizes_vs_vartime_multiscalar_mul() {
+        let mut rng = rand::thread_rng();
+        let B = constants::ED25519_BASEPOINT_POINT;
+
+        // Enough small scalars to use the separate Pippenger path, and
+        // too few to use it
+        for &n_small in [PIPPENGER_THRESHOLD + 10, 20].iter() {
+            let mut scalars = Vec::new();
+            let mut bit_lengths = Vec::new();
+            for i in 0..n_small {
+                let bits = [1usize, 8, 64][i % 3];
+                let small = rng.next_u64() & (u64::MAX >> (64 - bits));
+                scalars.push(Scalar::from(small));
+                bit_lengths.push(bits);
+            }
+            for _ in 0..5 {
+                scalars.push(Scalar::random(&mut rng));
+                bit_lengths.push(256);
+            }
+            let points: Vec<EdwardsPoint> = (0..scalars.len())
+                .map(|_| B * Scalar::random(&mut rng))
+                .collect();
+
+            let subject = EdwardsPoint::vartime_multiscalar_mul_with_scalar_sizes(
+                &scalars,
+                &points,
+                bit_lengths,
+            );
+            assert_eq!(subject, EdwardsPoint::vartime_multiscalar_mul(&scalars, &points));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn vartime_multiscalar_mul_with_scalar_sizes_rejects_large_scalar() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        EdwardsPoint::vartime_multiscalar_mul_with_scalar_sizes(&[Scalar::from(4u64)], &[B], vec![2]);
+    }
+
+    #[test]
+    fn vartime_multiscalar_mul_batched_vs_vartime_multiscalar_mul() {
+        let mut rng = rand::thread_rng();
+        let B = constants::ED25519_BASEPOINT_POINT;
+
+        // Sizes which use Straus and Pippenger respectively
+        for &n in [16usize, PIPPENGER_THRESHOLD].iter() {
+            let points: Vec<EdwardsPoint> = (0..n).map(|_| B * Scalar::random(&mut rng)).collect();
+            let scalar_vecs: Vec<Vec<Scalar>> = (0..3)
+                .map(|_| (0..n).map(|_| Scalar::random(&mut rng)).collect())
+                .collect();
+            let scalar_slices: Vec<&[Scalar]> = scalar_vecs.iter().map(|v| &v[..]).collect();
+
+            let subject = EdwardsPoint::vartime_multiscalar_mul_batched(&scalar_slices, &points);
+            let control: Vec<EdwardsPoint> = scalar_vecs
+                .iter()
+                .map(|scalars| EdwardsPoint::vartime_multiscalar_mul(scalars, &points))
+                .collect();
+            assert_eq!(subject, control);
+        }
+
+        assert!(EdwardsPoint::vartime_multiscalar_mul_batched(&[], &[B]).is_empty());
+    }
+
+    #[test]
+    fn multiscalar_mul_consumes_lazy_iterators_once() {
+        use core::cell::Cell;
+
+        let B = constants::ED25519_BASEPOINT_POINT;
+        // Sizes which use Straus and Pippenger respectively
+        for &n in [16usize, 300].iter() {
+            let scalars_taken = Cell::new(0);
+            let points_taken = Cell::new(0);
+            let scalars = (0..n).map(|i| {
+                scalars_taken.set(scalars_taken.get() + 1);
+                Scalar::from(i as u64 + 1)
+            });
+            let points = (0..n).map(|i| {
+                points_taken.set(points_taken.get() + 1);
+                B * Scalar::from(i as u64 * 3 + 2)
+            });
+
+            let expected: Scalar = (0..n as u64).map(|i| Scalar::from(i + 1) * Scalar::from(i * 3 + 2)).sum();
+
+            let result = EdwardsPoint::vartime_multiscalar_mul(scalars.clone(), points.clone());
+            assert_eq!(result, B * expected);
+            assert_eq!((scalars_taken.get(), points_taken.get()), (n, n));
+
+            let result = EdwardsPoint::multiscalar_mul(scalars, points);
+            assert_eq!(result, B * expected);
+            assert_eq!((scalars_taken.get(), points_taken.get()), (2 * n, 2 * n));
+        }
+    }
+
+    #[test]
+    fn scalar_mul_context_vs_scalar_mul() {
+        let mut rng = rand::thread_rng();
+        let P = constants::ED25519_BASEPOINT_POINT * Scalar::random(&mut rng)
+            + constants::EIGHT_TORSION[3];
+        let context = EdwardsScalarMulContext::new(&P);
+
+        for _ in 0..8 {
+            let s = Scalar::random(&mut rng);
+            assert_eq!(&context * &s, P * s);
+            assert_eq!(&s * &context, P * s);
+        }
+        assert!((&context * &Scalar::zero()).is_identity());
+        assert_eq!(&context * &Scalar::one(), P);
+    }
+
+    #[test]
+    fn mul_with_window_vs_scalar_mul() {
+        let mut rng = rand::thread_rng();
+        let P = constants::ED25519_BASEPOINT_POINT * Scalar::random(&mut rng)
+            + constants::EIGHT_TORSION[5];
+        let mut scalars = vec![
+            Scalar::zero(),
+            Scalar::one(),
+            -Scalar::one(),
+            // Unreduced, so the final radix-2^8 digit is a carry
+            Scalar::from_bits([0xff; 32]),
+        ];
+        scalars.extend((0..4).map(|_| Scalar::random(&mut rng)));
+
+        for w in 4..=8 {
+            for s in scalars.iter() {
+                assert_eq!(P.mul_with_window(s, w), P * s, "w = {}", w);
+            }
+        }
+    }
+
+    #[test]
+    fn mul_by_scalar_batch_vs_scalar_mul() {
+        let mut rng = rand::thread_rng();
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let points: Vec<EdwardsPoint> = constants::EIGHT_TORSION
+            .iter()
+            .map(|T| B * Scalar::random(&mut rng) + T)
+            .collect();
+
+        for k in [Scalar::zero(), -Scalar::one(), Scalar::random(&mut rng)].iter() {
+            let expected: Vec<EdwardsPoint> = points.iter().map(|P| P * k).collect();
+            assert_eq!(EdwardsPoint::mul_by_scalar_batch(&points, k), expected);
+        }
+        assert!(EdwardsPoint::mul_by_scalar_batch(&[], &Scalar::one()).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_with_window_rejects_large_window() {
+        constants::ED25519_BASEPOINT_POINT.mul_with_window(&Scalar::one(), 9);
+    }
+
+    #[test]
+    fn cached_point_add_sub() {
+        let mut rng = rand::thread_rng();
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let A = B * Scalar::random(&mut rng) + constants::EIGHT_TORSION[2];
+        let cached_A = EdwardsCachedPoint::from(&A);
+
+        for T in constants::EIGHT_TORSION.iter() {
+            let P = B * Scalar::random(&mut rng) + T;
+            assert_eq!(P + cached_A, P + A);
+            assert_eq!(P - &cached_A, P - A);
+
+            let mut Q = P;
+            Q += &cached_A;
+            Q -= cached_A;
+            assert_eq!(Q, P);
+        }
+        assert!((A - EdwardsCachedPoint::from(A)).is_identity());
+        assert_eq!(B + -cached_A, B - A);
+        assert_eq!(B - -&cached_A, B + A);
+    }
+
+    #[test]
+    fn optional_sum() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let points: Vec<EdwardsPoint> = (1..10u64)
+            .map(|i| B * Scalar::from(i) + constants::EIGHT_TORSION[i as usize % 8])
+            .collect();
+        let compressed: Vec<CompressedEdwardsY> = points.iter().map(|P| P.compress()).collect();
+
+        let sum: EdwardsPoint = points.iter().sum();
+        assert_eq!(EdwardsPoint::optional_sum(compressed.iter()), Some(sum));
+        assert_eq!(EdwardsPoint::optional_sum(compressed.clone()), Some(sum));
+        assert_eq!(
+            EdwardsPoint::optional_sum(Vec::<CompressedEdwardsY>::new()),
+            Some(EdwardsPoint::identity())
+        );
+
+        // An invalid encoding anywhere makes the sum fail
+        let mut bytes = [0u8; 32];
+        bytes[0] = 2;
+        let mut with_invalid = compressed.clone();
+        with_invalid.insert(4, CompressedEdwardsY(bytes));
+        assert_eq!(EdwardsPoint::optional_sum(with_invalid), None);
+    }
+
+    #[test]
+    fn compress_batch_matches_compress() {
+        let mut rng = rand::thread_rng();
+        let mut points: Vec<EdwardsPoint> = (0..32)
+            .map(|_| &constants::ED25519_BASEPOINT_TABLE * &Scalar::random(&mut rng))
+            .collect();
+        points.push(EdwardsPoint::identity());
+        points.push(constants::EIGHT_TORSION[3]);
+
+        let compressed = EdwardsPoint::compress_batch(&points);
+        assert_eq!(compressed.len(), points.len());
+        for (P, P_compressed) in points.iter().zip(compressed.iter()) {
+            assert_eq!(P.compress(), *P_compressed);
+        }
+
+        assert!(EdwardsPoint::compress_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn double_and_compress_batch_matches_double() {
+        let mut rng = rand::thread_rng();
+        let mut points: Vec<EdwardsPoint> = (0..32).map(|_| EdwardsPoint::random(&mut rng)).collect();
+        points.push(EdwardsPoint::identity());
+        points.push(constants::EIGHT_TORSION[2]);
+
+        let compressed = EdwardsPoint::double_and_compress_batch(&points);
+        assert_eq!(compressed.len(), points.len());
+        for (P, P2_compressed) in points.iter().zip(compressed.iter()) {
+            assert_eq!((P + P).compress(), *P2_compressed);
+        }
+    }
+
+    #[test]
+    fn to_affine_is_projective_normalization() {
+        let mut rng = rand::thread_rng();
+        let P = EdwardsPoint::random(&mut rng);
+        // Scale the projective coordinates, which must not change the affine ones
+        let k = FieldElement::from_bytes(&[7u8; 32]);
+        let Q = EdwardsPoint{ X: &P.X * &k, Y: &P.Y * &k, Z: &P.Z * &k, T: &P.T * &k };
+        assert_eq!(P.to_affine(), Q.to_affine());
+
+        let (x, y) = P.to_affine();
+        let R = EdwardsPoint{ X: x, Y: y, Z: FieldElement::one(), T: &x * &y };
+        assert!(R.is_valid());
+        assert_eq!(P, R);
+
+        assert_eq!(EdwardsPoint::identity().to_affine(), (FieldElement::zero(), FieldElement::one()));
+    }
+
+    #[test]
+    fn uncompressed_encoding_round_trip() {
+        let mut rng = rand::thread_rng();
+        let mut points: Vec<EdwardsPoint> = (0..16)
+            .map(|_| &constants::ED25519_BASEPOINT_TABLE * &Scalar::random(&mut rng))
+            .collect();
+        points.extend_from_slice(&constants::EIGHT_TORSION);
+
+        for P in points.iter() {
+            let bytes = P.to_bytes_uncompressed();
+            let Q = EdwardsPoint::from_bytes_uncompressed(&bytes).unwrap();
+            assert!(Q.is_valid());
+            assert_eq!(*P, Q);
+            assert_eq!(P.compress(), Q.compress());
+
+            // The y-coordinate matches the compressed encoding, minus the sign bit
+            let mut y_bytes = P.compress().to_bytes();
+            y_bytes[31] &= 0x7f;
+            assert_eq!(&bytes[32..], &y_bytes[..]);
+        }
+    }
+
+    #[test]
+    fn uncompressed_encoding_rejects_invalid() {
+        let B_bytes = constants::ED25519_BASEPOINT_POINT.to_bytes_uncompressed();
+
+        // Not on the curve
+        let mut bytes = B_bytes;
+        bytes[0] ^= 1;
+        assert!(EdwardsPoint::from_bytes_uncompressed(&bytes).is_none());
+
+        // Non-canonical: the identity (0, 1) with y encoded as p + 1
+        let mut bytes = [0u8; 64];
+        bytes[32] = 0xee;
+        for b in bytes[33..63].iter_mut() {
+            *b = 0xff;
+        }
+        bytes[63] = 0x7f;
+        assert!(EdwardsPoint::from_bytes_uncompressed(&bytes).is_none());
+        bytes[32] = 1;
+        for b in bytes[33..].iter_mut() {
+            *b = 0;
+        }
+        assert_eq!(
+            EdwardsPoint::from_bytes_uncompressed(&bytes),
+            Some(EdwardsPoint::identity())
+        );
+    }
+
+    /// Test mul_base versus a known scalar multiple from ed25519.py
+    #[test]
+    fn mul_base_vs_ed25519py() {
+        let aB = EdwardsPoint::mul_base(&A_SCALAR);
+        assert_eq!(aB.compress(), A_TIMES_BASEPOINT);
+    }
+
     /// Test scalar_mul versus a known scalar multiple from ed25519.py
     #[test]
     fn scalar_mul_vs_ed25519py() {
@@ -1202,6 +4580,25 @@ mod test {
         assert_eq!(p1, bp);
     }
 
+    #[test]
+    fn conditional_negate() {
+        use subtle::ConditionallyNegatable;
+
+        let P = constants::ED25519_BASEPOINT_POINT * Scalar::from(17u64)
+            + constants::EIGHT_TORSION[3];
+
+        let mut Q = P;
+        Q.conditional_negate(Choice::from(0));
+        assert_eq!(Q.compress(), P.compress());
+
+        Q.conditional_negate(Choice::from(1));
+        assert_eq!(Q.compress(), (-P).compress());
+        assert_eq!(Q.X, -(&P.X));
+        assert_eq!(Q.Y, P.Y);
+        assert_eq!(Q.Z, P.Z);
+        assert_eq!(Q.T, -(&P.T));
+    }
+
     #[test]
     fn is_small_order() {
         // The basepoint has large prime order
@@ -1212,16 +4609,212 @@ mod test {
         }
     }
 
+    #[test]
+    fn conditional_select_from_picks_index() {
+        let mut points = [EdwardsPoint::identity(); 16];
+        let mut P = constants::ED25519_BASEPOINT_POINT;
+        for entry in points.iter_mut() {
+            *entry = P;
+            P += constants::ED25519_BASEPOINT_POINT;
+        }
+        for i in 0..16 {
+            assert_eq!(EdwardsPoint::conditional_select_from(&points, i as u8), points[i]);
+        }
+        assert_eq!(EdwardsPoint::conditional_select_from(&points, 16), EdwardsPoint::identity());
+        assert_eq!(EdwardsPoint::conditional_select_from(&[], 0), EdwardsPoint::identity());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn sum_of_products_vs_multiscalar_mul() {
+        let mut rng = rand::thread_rng();
+        let scalars: Vec<Scalar> = (0..8).map(|_| Scalar::random(&mut rng)).collect();
+        let points: Vec<EdwardsPoint> = scalars.iter()
+            .map(|s| constants::ED25519_BASEPOINT_TABLE.basepoint_mul(&s.invert()))
+            .collect();
+
+        let expected = EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+        assert_eq!(EdwardsPoint::sum_of_products(&scalars, &points), expected);
+        assert_eq!(EdwardsPoint::vartime_sum_of_products(&scalars, &points), expected);
+        assert_eq!(EdwardsPoint::sum_of_products(&[], &[]), EdwardsPoint::identity());
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "alloc")]
+    fn sum_of_products_rejects_mismatched_lengths() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        EdwardsPoint::sum_of_products(&[Scalar::one()], &[B, B]);
+    }
+
+    #[test]
+    fn alternate_debug_prints_affine_coordinates() {
+        // Scale the projective coordinates so that Z != 1.
+        let B = constants::ED25519_BASEPOINT_POINT.double() - constants::ED25519_BASEPOINT_POINT;
+        assert_eq!(
+            format!("{:#?}", B),
+            "EdwardsPoint {\n    \
+             x: 0x216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a,\n    \
+             y: 0x6666666666666666666666666666666666666666666666666666666666666658,\n}"
+        );
+        assert!(format!("{:?}", B).starts_with("EdwardsPoint{\n\tX: "));
+    }
+
+    #[test]
+    fn basepoint_table_double_mul() {
+        let G = constants::ED25519_BASEPOINT_POINT;
+        let H = G * Scalar::from(8475983829u64);
+        let a = A_SCALAR;
+        let b = B_SCALAR;
+        let expected = G * a + H * b;
+
+        macro_rules! check_double_mul {
+            ($name:ident) => {
+                let table_g = $name::create(&G);
+                let table_h = $name::create(&H);
+                assert_eq!($name::double_mul(&table_g, &a, &table_h, &b), expected);
+                assert_eq!($name::double_mul(&table_g, &a, &table_h, &Scalar::zero()), G * a);
+            };
+        }
+
+        check_double_mul!(EdwardsBasepointTable);
+        check_double_mul!(EdwardsBasepointTableRadix32);
+        check_double_mul!(EdwardsBasepointTableRadix64);
+        check_double_mul!(EdwardsBasepointTableRadix128);
+        check_double_mul!(EdwardsBasepointTableRadix256);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vartime_multiscalar_mul_streaming_vs_vartime_multiscalar_mul() {
+        let mut rng = rand::thread_rng();
+        let scalars: Vec<Scalar> = (0..200).map(|_| Scalar::random(&mut rng)).collect();
+        let points: Vec<EdwardsPoint> = scalars.iter()
+            .map(|s| constants::ED25519_BASEPOINT_POINT * s.invert())
+            .collect();
+
+        let expected = EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+        for w in 4..=8 {
+            // Filtering hides the lengths of the iterators.
+            let result = EdwardsPoint::vartime_multiscalar_mul_streaming(
+                w,
+                scalars.iter().filter(|_| true),
+                points.iter().filter(|_| true),
+            );
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "alloc")]
+    fn vartime_multiscalar_mul_streaming_rejects_mismatched_lengths() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        EdwardsPoint::vartime_multiscalar_mul_streaming(6, &[Scalar::one(); 2], &[B; 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn basepoint_table_bytes_roundtrip() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let P = B * Scalar::from(8475983829u64);
+
+        // The larger tables are hundreds of KB, and unoptimized builds
+        // keep several copies on the stack, so check each table type
+        // on its own thread with a larger stack.
+        macro_rules! check_roundtrip {
+            ($name:ident) => {{
+                fn check(B: EdwardsPoint, P: EdwardsPoint) {
+                    let mut bytes = $name::create(&P).to_bytes();
+
+                    let restored = $name::from_bytes_verified(&bytes, &P).unwrap();
+                    assert_eq!(restored.to_bytes(), bytes);
+                    assert_eq!(&restored * &A_SCALAR, P * A_SCALAR);
+
+                    assert!($name::from_bytes_verified(&bytes, &B).is_none());
+                    assert!($name::from_bytes_verified(&bytes[1..], &P).is_none());
+
+                    // Corrupt the last entry of the last lookup table.
+                    let last = bytes.len() - 1;
+                    bytes[last - 40] ^= 1;
+                    assert!($name::from_bytes_verified(&bytes, &P).is_none());
+                }
+                ::std::thread::Builder::new()
+                    .stack_size(8 << 20)
+                    .spawn(move || check(B, P))
+                    .unwrap()
+                    .join()
+                    .unwrap();
+            }};
+        }
+
+        check_roundtrip!(EdwardsBasepointTable);
+        check_roundtrip!(EdwardsBasepointTableRadix32);
+        check_roundtrip!(EdwardsBasepointTableRadix64);
+        check_roundtrip!(EdwardsBasepointTableRadix128);
+        check_roundtrip!(EdwardsBasepointTableRadix256);
+
+        let bytes = constants::ED25519_BASEPOINT_TABLE.to_bytes();
+        let restored = EdwardsBasepointTable::from_bytes_verified(&bytes, &B).unwrap();
+        assert_eq!(restored.basepoint(), B);
+    }
+
+    #[test]
+    fn ct_eq_batch() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let a: Vec<EdwardsPoint> = (1..9u64).map(|i| B * Scalar::from(i)).collect();
+        // Same points, with different projective coordinates
+        let b: Vec<EdwardsPoint> = a.iter().map(|P| P.double() - P).collect();
+
+        assert_eq!(EdwardsPoint::ct_eq_batch(&a, &b).unwrap_u8(), 1);
+        assert_eq!(EdwardsPoint::ct_eq_batch(&[], &[]).unwrap_u8(), 1);
+        assert_eq!(EdwardsPoint::ct_eq_batch(&a, &b[1..]).unwrap_u8(), 0);
+
+        for i in 0..a.len() {
+            let mut c = b.clone();
+            c[i] += constants::EIGHT_TORSION[1];
+            assert_eq!(EdwardsPoint::ct_eq_batch(&a, &c).unwrap_u8(), 0);
+        }
+    }
+
+    #[test]
+    fn ct_is_small_order_vs_is_small_order() {
+        let mut rng = rand::thread_rng();
+        let B = constants::ED25519_BASEPOINT_POINT;
+        for T in constants::EIGHT_TORSION.iter() {
+            // Scale the projective coordinates, which must not matter
+            let T_scaled = EdwardsPoint {
+                X: &T.X * &constants::EDWARDS_D,
+                Y: &T.Y * &constants::EDWARDS_D,
+                Z: &T.Z * &constants::EDWARDS_D,
+                T: &T.T * &constants::EDWARDS_D,
+            };
+            assert!(bool::from(T.ct_is_small_order()));
+            assert!(bool::from(T_scaled.ct_is_small_order()));
+
+            let P = B * Scalar::random(&mut rng) + T;
+            assert!(!bool::from(P.ct_is_small_order()));
+            assert_eq!(bool::from(P.ct_is_small_order()), P.is_small_order());
+        }
+    }
+
     #[test]
     fn compressed_identity() {
         assert_eq!(EdwardsPoint::identity().compress(),
                    CompressedEdwardsY::identity());
+        assert_eq!(EdwardsPoint::IDENTITY.compress(),
+                   CompressedEdwardsY::IDENTITY);
     }
 
     #[test]
     fn is_identity() {
         assert!(   EdwardsPoint::identity().is_identity() == true);
         assert!(constants::ED25519_BASEPOINT_POINT.is_identity() == false);
+        assert_eq!(EdwardsPoint::identity().ct_is_identity().unwrap_u8(), 1);
+        assert_eq!(constants::ED25519_BASEPOINT_POINT.ct_is_identity().unwrap_u8(), 0);
+        // A projectively scaled identity is still the identity
+        let B = constants::ED25519_BASEPOINT_POINT;
+        assert_eq!((B - B).ct_is_identity().unwrap_u8(), 1);
     }
 
     /// Rust's debug builds have overflow and underflow trapping,
@@ -1287,6 +4880,26 @@ mod test {
         assert_eq!(H2, H3);
     }
 
+    #[test]
+    fn vartime_multiscalar_mul_with_scratch_matches_allocating() {
+        let mut rng = rand::thread_rng();
+        let mut scratch = MsmScratch::new(16);
+
+        // Reuse one scratch space across sizes on both sides of the
+        // Straus/Pippenger crossover, growing and shrinking it.
+        for &n in &[0, 1, 16, 100, 300, 16, 600] {
+            let xs = (0..n).map(|_| Scalar::random(&mut rng)).collect::<Vec<_>>();
+            let Gs = xs.iter()
+                .map(|xi| xi * &constants::ED25519_BASEPOINT_TABLE)
+                .collect::<Vec<_>>();
+
+            let expected = EdwardsPoint::vartime_multiscalar_mul(&xs, &Gs);
+            let result = EdwardsPoint::vartime_multiscalar_mul_with_scratch(&mut scratch, &xs, &Gs);
+
+            assert_eq!(result.compress(), expected.compress());
+        }
+    }
+
     // Use different multiscalar sizes to hit different internal
     // parameters.
 
@@ -1365,6 +4978,45 @@ mod test {
         assert_eq!(Q.compress(), R.compress());
     }
 
+    #[test]
+    fn vartime_precomputation_bytes_roundtrip() {
+        use traits::VartimePrecomputedMultiscalarMul;
+
+        let mut rng = rand::thread_rng();
+        let static_points: Vec<EdwardsPoint> = (0..4).map(|_| EdwardsPoint::random(&mut rng)).collect();
+        let static_scalars: Vec<Scalar> = (0..4).map(|_| Scalar::random(&mut rng)).collect();
+        let dynamic_points = [EdwardsPoint::random(&mut rng)];
+        let dynamic_scalars = [Scalar::random(&mut rng)];
+
+        let precomputation = VartimeEdwardsPrecomputation::new(static_points.iter());
+        let bytes = precomputation.to_bytes();
+        assert_eq!(bytes.len(), 4 * 6144);
+
+        let restored = VartimeEdwardsPrecomputation::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.to_bytes(), bytes);
+        assert_eq!(
+            restored.vartime_mixed_multiscalar_mul(&static_scalars, &dynamic_scalars, &dynamic_points),
+            precomputation.vartime_mixed_multiscalar_mul(&static_scalars, &dynamic_scalars, &dynamic_points)
+        );
+
+        assert!(VartimeEdwardsPrecomputation::from_bytes(&[]).is_some());
+        assert!(VartimeEdwardsPrecomputation::from_bytes(&bytes[..6143]).is_none());
+
+        // Swapping two entries gives well-formed points in the wrong places
+        let mut swapped = bytes.clone();
+        for i in 0..96 {
+            swapped.swap(96 + i, 2 * 96 + i);
+        }
+        assert!(VartimeEdwardsPrecomputation::from_bytes(&swapped).is_none());
+
+        // Corrupting any one field element is detected
+        for offset in [0, 40, 70, 6143, 3 * 6144 + 1000].iter() {
+            let mut corrupted = bytes.clone();
+            corrupted[*offset] ^= 1;
+            assert!(VartimeEdwardsPrecomputation::from_bytes(&corrupted).is_none());
+        }
+    }
+
     mod vartime {
         use super::super::*;
         use super::{A_SCALAR, B_SCALAR, A_TIMES_BASEPOINT, DOUBLE_SCALAR_MULT_RESULT};
@@ -1377,6 +5029,35 @@ mod test {
             assert_eq!(result.compress(), DOUBLE_SCALAR_MULT_RESULT);
         }
 
+        #[test]
+        fn double_scalar_mul_vs_ed25519py() {
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let result = EdwardsPoint::vartime_double_scalar_mul(
+                &A_SCALAR,
+                &A,
+                &B_SCALAR,
+                &constants::ED25519_BASEPOINT_POINT,
+            );
+            assert_eq!(result.compress(), DOUBLE_SCALAR_MULT_RESULT);
+        }
+
+        #[test]
+        fn double_scalar_mul_vs_naive() {
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let B = constants::ED25519_BASEPOINT_POINT * B_SCALAR;
+            let a = A_SCALAR * B_SCALAR;
+            let b = A_SCALAR + B_SCALAR;
+
+            let result = EdwardsPoint::vartime_double_scalar_mul(&a, &A, &b, &B);
+            assert_eq!(result.compress(), (A * a + B * b).compress());
+
+            let result = EdwardsPoint::vartime_double_scalar_mul(&Scalar::zero(), &A, &b, &B);
+            assert_eq!(result.compress(), (B * b).compress());
+
+            let result = EdwardsPoint::vartime_double_scalar_mul(&a, &A, &Scalar::zero(), &B);
+            assert_eq!(result.compress(), (A * a).compress());
+        }
+
         #[test]
         fn multiscalar_mul_vs_ed25519py() {
             let A = A_TIMES_BASEPOINT.decompress().unwrap();
@@ -1403,6 +5084,67 @@ mod test {
         }
     }
 
+    #[test]
+    fn multiscalar_mul_array_vs_multiscalar_mul() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let scalars: [Scalar; 16] = ::core::array::from_fn(|i| Scalar::from(i as u64 + 2).invert());
+        let points: [EdwardsPoint; 16] = ::core::array::from_fn(|i| {
+            B * Scalar::from(i as u64 + 1) + constants::EIGHT_TORSION[i % 8]
+        });
+
+        assert_eq!(EdwardsPoint::multiscalar_mul_array(&scalars, &points),
+                   EdwardsPoint::multiscalar_mul(&scalars, &points));
+
+        let pair = EdwardsPoint::multiscalar_mul_array(&[scalars[0], scalars[1]], &[points[0], points[1]]);
+        assert_eq!(pair, EdwardsPoint::multiscalar_mul(&scalars[..2], &points[..2]));
+
+        assert!(EdwardsPoint::multiscalar_mul_array(&[], &[]).is_identity());
+    }
+
+    #[test]
+    #[cfg(feature = "group")]
+    fn group_encoding_roundtrip() {
+        use group::GroupEncoding;
+
+        let P = constants::ED25519_BASEPOINT_POINT * A_SCALAR;
+        let bytes = P.to_bytes();
+        assert_eq!(bytes, P.compress().to_bytes());
+        assert_eq!(EdwardsPoint::from_bytes(&bytes).unwrap(), P);
+        assert_eq!(EdwardsPoint::from_bytes_unchecked(&bytes).unwrap(), P);
+
+        // y = 2 is not the y-coordinate of a curve point
+        let mut invalid = [0u8; 32];
+        invalid[0] = 2;
+        assert!(CompressedEdwardsY(invalid).decompress().is_none());
+        assert!(bool::from(EdwardsPoint::from_bytes(&invalid).is_none()));
+    }
+
+    #[test]
+    #[cfg(feature = "group")]
+    fn group_ops() {
+        use group::Group;
+
+        let B = <EdwardsPoint as Group>::generator();
+        assert_eq!(B, constants::ED25519_BASEPOINT_POINT);
+        assert!(bool::from(Group::is_identity(&<EdwardsPoint as Group>::identity())));
+        assert!(!bool::from(Group::is_identity(&B)));
+        assert_eq!(Group::double(&B), B + B);
+        assert_eq!(B * A_SCALAR, &constants::ED25519_BASEPOINT_TABLE * &A_SCALAR);
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize_resets_to_identity() {
+        use zeroize::Zeroize;
+
+        let mut P = constants::ED25519_BASEPOINT_POINT * A_SCALAR;
+        P.zeroize();
+        assert!(P.is_identity());
+        assert!(P.is_valid());
+        assert_eq!(P.X.to_bytes(), [0u8; 32]);
+        assert_eq!(P.T.to_bytes(), [0u8; 32]);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn serde_bincode_basepoint_roundtrip() {
@@ -1418,4 +5160,29 @@ mod test {
         assert_eq!(dec_uncompressed, constants::ED25519_BASEPOINT_POINT);
         assert_eq!(dec_compressed, constants::ED25519_BASEPOINT_COMPRESSED);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_json_basepoint_roundtrip() {
+        use serde_json;
+
+        let B_hex = "\"5866666666666666666666666666666666666666666666666666666666666666\"";
+
+        let encoded = serde_json::to_string(&constants::ED25519_BASEPOINT_POINT).unwrap();
+        let enc_compressed = serde_json::to_string(&constants::ED25519_BASEPOINT_COMPRESSED).unwrap();
+        assert_eq!(encoded, B_hex);
+        assert_eq!(enc_compressed, B_hex);
+
+        let dec_uncompressed: EdwardsPoint = serde_json::from_str(&encoded).unwrap();
+        let dec_compressed: CompressedEdwardsY = serde_json::from_str(&encoded).unwrap();
+
+        assert_eq!(dec_uncompressed, constants::ED25519_BASEPOINT_POINT);
+        assert_eq!(dec_compressed, constants::ED25519_BASEPOINT_COMPRESSED);
+
+        // Uppercase hex is accepted; bad lengths and digits are not.
+        let upper: EdwardsPoint = serde_json::from_str(&B_hex.to_uppercase()).unwrap();
+        assert_eq!(upper, constants::ED25519_BASEPOINT_POINT);
+        assert!(serde_json::from_str::<CompressedEdwardsY>("\"5866\"").is_err());
+        assert!(serde_json::from_str::<CompressedEdwardsY>(&B_hex.replace("58", "zz")).is_err());
+    }
 }
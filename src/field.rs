@@ -28,6 +28,7 @@ use subtle::ConditionallySelectable;
 use subtle::ConditionallyNegatable;
 use subtle::Choice;
 use subtle::ConstantTimeEq;
+use subtle::CtOption;
 
 use constants;
 use backend;
@@ -64,6 +65,26 @@ impl ConstantTimeEq for FieldElement {
     /// Test equality between two `FieldElement`s.  Since the
     /// internal representation is not canonical, the field elements
     /// are normalized to wire format before comparison.
+    ///
+    /// This runs in constant time, so it can be used on secret
+    /// values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # extern crate subtle;
+    /// use curve25519_dalek::field::FieldElement;
+    /// use subtle::ConstantTimeEq;
+    ///
+    /// # fn main() {
+    /// let a = FieldElement::from_u64(7);
+    /// let b = &FieldElement::from_u64(3) + &FieldElement::from_u64(4);
+    ///
+    /// assert!(bool::from(a.ct_eq(&b)));
+    /// assert!(!bool::from(a.ct_eq(&FieldElement::one())));
+    /// # }
+    /// ```
     fn ct_eq(&self, other: &FieldElement) -> Choice {
         self.to_bytes().ct_eq(&other.to_bytes())
     }
@@ -82,7 +103,11 @@ impl FieldElement {
         (bytes[0] & 1).into()
     }
 
-    /// Determine if this `FieldElement` is zero.
+    /// Determine if this `FieldElement` is zero, in constant time.
+    ///
+    /// Like `ct_eq`, this checks the canonical encoding, so it also
+    /// recognizes unreduced representations of zero, such as the one
+    /// obtained by decoding \\(p\\) itself.
     ///
     /// # Return
     ///
@@ -94,6 +119,22 @@ impl FieldElement {
         bytes.ct_eq(&zero)
     }
 
+    /// Construct the field element corresponding to the integer `n`.
+    pub fn from_u64(n: u64) -> FieldElement {
+        use byteorder::{ByteOrder, LittleEndian};
+        let mut bytes = [0u8; 32];
+        LittleEndian::write_u64(&mut bytes, n);
+        FieldElement::from_bytes(&bytes)
+    }
+
+    /// Construct the field element corresponding to the integer `n`.
+    pub fn from_u128(n: u128) -> FieldElement {
+        use byteorder::{ByteOrder, LittleEndian};
+        let mut bytes = [0u8; 32];
+        LittleEndian::write_u128(&mut bytes, n);
+        FieldElement::from_bytes(&bytes)
+    }
+
     /// Compute (self^(2^250-1), self^11), used as a helper function
     /// within invert() and pow22523().
     fn pow22501(&self) -> (FieldElement, FieldElement) {
@@ -134,7 +175,10 @@ impl FieldElement {
 
     /// Given a slice of public `FieldElements`, replace each with its inverse.
     ///
-    /// All input `FieldElements` **MUST** be nonzero.
+    /// This uses Montgomery's trick to compute all of the inverses
+    /// with a single field inversion.  Zero inputs are left as zero,
+    /// matching the behaviour of `invert`, and do not affect the
+    /// inverses of the other elements.
     #[cfg(feature = "alloc")]
     pub fn batch_invert(inputs: &mut [FieldElement]) {
         // Montgomery’s Trick and Fast Implementation of Masked AES
@@ -148,20 +192,24 @@ impl FieldElement {
         let mut acc = FieldElement::one();
 
         // Pass through the input vector, recording the previous
-        // products in the scratch space
+        // products in the scratch space.  Zero inputs are replaced
+        // by one so that they don't zero out the accumulator.
         for (input, scratch) in inputs.iter().zip(scratch.iter_mut()) {
             *scratch = acc;
-            acc = &acc * input;
+            let input_or_one = FieldElement::conditional_select(input, &FieldElement::one(), input.is_zero());
+            acc = &acc * &input_or_one;
         }
 
         // Compute the inverse of all products
         acc = acc.invert();
 
         // Pass through the vector backwards to compute the inverses
-        // in place
+        // in place, skipping over the zero inputs
         for (input, scratch) in inputs.iter_mut().rev().zip(scratch.into_iter().rev()) {
-            let tmp = &acc * input;
-            *input = &acc * &scratch;
+            let input_is_zero = input.is_zero();
+            let input_or_one = FieldElement::conditional_select(input, &FieldElement::one(), input_is_zero);
+            let tmp = &acc * &input_or_one;
+            input.conditional_assign(&(&acc * &scratch), !input_is_zero);
             acc = tmp;
         }
     }
@@ -183,8 +231,55 @@ impl FieldElement {
         t21
     }
 
+    /// Raise this field element to the power `exp`, given as 32
+    /// little-endian bytes.
+    ///
+    /// This is constant-time with respect to both `self` and `exp`.
+    /// It uses a fixed 4-bit window: each of the 64 windows costs
+    /// four squarings and one multiplication by an entry of a table
+    /// of \\(x\^0, \ldots, x\^{15}\\), which is read in constant
+    /// time.
+    ///
+    /// Since exponents are taken as integers (not reduced mod \\(p\\)),
+    /// they are passed as bytes rather than as a `FieldElement`.
+    pub fn pow(&self, exp: &[u8; 32]) -> FieldElement {
+        let mut table = [FieldElement::one(); 16];
+        for i in 1..16 {
+            table[i] = &table[i - 1] * self;
+        }
+
+        let mut acc = FieldElement::one();
+        for i in (0..64).rev() {
+            let nibble = (exp[i >> 1] >> ((i & 1) * 4)) & 15;
+
+            let mut t = FieldElement::one();
+            for (j, entry) in table.iter().enumerate() {
+                t.conditional_assign(entry, (j as u8).ct_eq(&nibble));
+            }
+
+            acc = &acc.pow2k(4) * &t;
+        }
+
+        acc
+    }
+
     /// Raise this field element to the power (p-5)/8 = 2^252 -3.
-    fn pow_p58(&self) -> FieldElement {
+    ///
+    /// This is the exponentiation at the heart of `sqrt_ratio_i`,
+    /// computed in constant time with a fixed addition chain, which
+    /// shares its first part with `invert`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::field::FieldElement;
+    ///
+    /// // Since 8 * (p-5)/8 + 5 = p, we have (x^((p-5)/8))^8 * x^5 = x^p = x.
+    /// let x = FieldElement::from_u64(1234567);
+    /// let x5 = &x.pow2k(2) * &x;
+    /// assert_eq!(&x.pow_p58().pow2k(3) * &x5, x);
+    /// ```
+    pub fn pow_p58(&self) -> FieldElement {
         // The bits of (p-5)/8 are 101111.....11.
         //
         //                                 nonzero bits of exponent
@@ -199,6 +294,18 @@ impl FieldElement {
     /// or `sqrt(i*u/v)` in constant time.
     ///
     /// This function always returns the nonnegative square root.
+    /// A field element is nonnegative if `is_negative` returns
+    /// `Choice(0)`, that is, if the low bit of its canonical encoding
+    /// is clear.  A nonzero square has exactly two square roots,
+    /// \\(r\\) and \\(-r\\), and exactly one of them is nonnegative,
+    /// so the result is uniquely determined.
+    ///
+    /// Here \\(i\\) is the square root of \\(-1\\) given by
+    /// \\(2\^{(p-1)/4}\\), which is nonnegative.  Since
+    /// \\(p \equiv 5 \pmod 8\\), \\(-1\\) is a square and \\(i\\) is
+    /// not, so when \\(u/v\\) is a nonzero nonsquare, \\(iu/v\\) is a
+    /// square and its root is returned instead.  The Ristretto
+    /// encoding relies on these conventions.
     ///
     /// # Return
     ///
@@ -207,6 +314,34 @@ impl FieldElement {
     /// - `(Choice(0), zero)        ` if `v` is zero and `u` is nonzero;
     /// - `(Choice(0), +sqrt(i*u/v))` if `u/v` is nonsquare (so `i*u/v` is square).
     ///
+    /// # Cost
+    ///
+    /// The cost is dominated by one exponentiation by \\((p-5)/8\\),
+    /// which computes the square root and the division together.
+    /// This is not an inversion, so it cannot be shared between
+    /// several inputs with Montgomery's trick the way
+    /// `batch_invert` shares inversions: each input still needs its
+    /// own exponentiation, so there is no batched version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::field::FieldElement;
+    ///
+    /// let one = FieldElement::one();
+    /// let two = FieldElement::from_u64(2);
+    ///
+    /// // 4 is a square, with nonnegative root 2
+    /// let (was_square, r) = FieldElement::sqrt_ratio_i(&FieldElement::from_u64(4), &one);
+    /// assert!(bool::from(was_square));
+    /// assert_eq!(r, two);
+    ///
+    /// // 2 is not a square mod p, so r = sqrt(2i), and r^4 = (2i)^2 = -4
+    /// let (was_square, r) = FieldElement::sqrt_ratio_i(&two, &one);
+    /// assert!(!bool::from(was_square));
+    /// assert!(!bool::from(r.is_negative()));
+    /// assert_eq!(r.square().square(), -&FieldElement::from_u64(4));
+    /// ```
     pub fn sqrt_ratio_i(u: &FieldElement, v: &FieldElement) -> (Choice, FieldElement) {
         // Using the same trick as in ed25519 decoding, we merge the
         // inversion, the square root, and the square test as follows.
@@ -270,6 +405,28 @@ impl FieldElement {
     pub fn invsqrt(&self) -> (Choice, FieldElement) {
         FieldElement::sqrt_ratio_i(&FieldElement::one(), self)
     }
+
+    /// Attempt to compute `sqrt(self)` in constant time.
+    ///
+    /// Convenience wrapper around `sqrt_ratio_i`, with `v = 1`.
+    ///
+    /// # Return
+    ///
+    /// - `Some(+sqrt(self))` if `self` is a square (including zero),
+    ///   where the nonnegative square root is chosen;
+    /// - `None` if `self` is a nonsquare.
+    pub fn sqrt(&self) -> CtOption<FieldElement> {
+        let (is_square, r) = FieldElement::sqrt_ratio_i(self, &FieldElement::one());
+        CtOption::new(r, is_square)
+    }
+
+    /// Determine in constant time whether `self` is a square, i.e.,
+    /// whether its Legendre symbol is not \\(-1\\).
+    ///
+    /// Zero is considered a square.
+    pub fn is_square(&self) -> Choice {
+        self.sqrt().is_some()
+    }
 }
 
 #[cfg(test)]
@@ -307,6 +464,25 @@ mod test {
          0xe0, 0xf0, 0xb8, 0x1b, 0x20, 0xd2, 0xb5, 0x1f,
          0x15, 0x21, 0xf9, 0xe3, 0xe1, 0x61, 0x21, 0x55];
 
+    #[test]
+    fn from_integers() {
+        use byteorder::{ByteOrder, LittleEndian};
+
+        for n in [0u64, 1, 19, 0xdead_beef, 0xffff_ffff_ffff_ffff].iter() {
+            let mut expected = [0u8; 32];
+            LittleEndian::write_u64(&mut expected, *n);
+            assert_eq!(FieldElement::from_u64(*n).to_bytes(), expected);
+            assert_eq!(FieldElement::from_u128(*n as u128).to_bytes(), expected);
+        }
+
+        let a = 0xffff_ffff_ffff_ffffu64;
+        let b = 0x1234_5678_9abc_def0u64;
+        assert_eq!(&FieldElement::from_u64(a) * &FieldElement::from_u64(b),
+                   FieldElement::from_u128((a as u128) * (b as u128)));
+        assert_eq!(FieldElement::from_u64(0), FieldElement::zero());
+        assert_eq!(FieldElement::from_u64(1), FieldElement::one());
+    }
+
     #[test]
     fn a_mul_a_vs_a_squared_constant() {
         let a = FieldElement::from_bytes(&A_BYTES);
@@ -391,6 +567,30 @@ mod test {
         assert_eq!(sqrt.is_negative().unwrap_u8(), 0);
     }
 
+    #[test]
+    fn sqrt_and_is_square() {
+        let a    = FieldElement::from_bytes(&A_BYTES);
+        let asq  = FieldElement::from_bytes(&ASQ_BYTES);
+
+        let r = asq.sqrt().unwrap();
+        assert_eq!(r.square(), asq);
+        assert!(r == a || r == -&a);
+        assert_eq!(r.is_negative().unwrap_u8(), 0);
+        assert_eq!(asq.is_square().unwrap_u8(), 1);
+
+        assert_eq!(FieldElement::zero().sqrt().unwrap(), FieldElement::zero());
+        assert_eq!(FieldElement::zero().is_square().unwrap_u8(), 1);
+
+        // 2 is a nonsquare mod p, and so is 2 times any nonzero square
+        let two = &FieldElement::one() + &FieldElement::one();
+        assert_eq!(two.sqrt().is_none().unwrap_u8(), 1);
+        assert_eq!(two.is_square().unwrap_u8(), 0);
+        assert_eq!((&two * &asq).is_square().unwrap_u8(), 0);
+
+        // -1 is a square, since p = 1 mod 4
+        assert_eq!(FieldElement::minus_one().sqrt().unwrap().square(), FieldElement::minus_one());
+    }
+
     #[test]
     fn a_p58_vs_ap58_constant() {
         let a    = FieldElement::from_bytes(&A_BYTES);
@@ -398,6 +598,35 @@ mod test {
         assert_eq!(ap58, a.pow_p58());
     }
 
+    #[test]
+    fn pow_vs_known_exponents() {
+        let a    = FieldElement::from_bytes(&A_BYTES);
+        let asq  = FieldElement::from_bytes(&ASQ_BYTES);
+        let ainv = FieldElement::from_bytes(&AINV_BYTES);
+        let ap58 = FieldElement::from_bytes(&AP58_BYTES);
+
+        let mut exp = [0u8; 32];
+        assert_eq!(a.pow(&exp), FieldElement::one());
+        exp[0] = 1;
+        assert_eq!(a.pow(&exp), a);
+        exp[0] = 2;
+        assert_eq!(a.pow(&exp), asq);
+        assert_eq!(a.pow(&exp), a.square());
+
+        // (p-5)/8 = 2^252 - 3
+        let mut p58 = [0xffu8; 32];
+        p58[0] = 0xfd;
+        p58[31] = 0x0f;
+        assert_eq!(a.pow(&p58), ap58);
+        assert_eq!(a.pow(&p58), a.pow_p58());
+
+        // p - 2 = 2^255 - 21
+        let mut pm2 = [0xffu8; 32];
+        pm2[0] = 0xeb;
+        pm2[31] = 0x7f;
+        assert_eq!(a.pow(&pm2), ainv);
+    }
+
     #[test]
     fn equality() {
         let a    = FieldElement::from_bytes(&A_BYTES);
@@ -406,6 +635,24 @@ mod test {
         assert!(a != ainv);
     }
 
+    #[test]
+    fn ct_eq_and_is_zero_use_canonical_encoding() {
+        // p = 2^255 - 19 is not a canonical encoding, but it decodes to zero.
+        let mut p_bytes = [0xffu8; 32];
+        p_bytes[0] = 0xed;
+        p_bytes[31] = 0x7f;
+        let p = FieldElement::from_bytes(&p_bytes);
+
+        assert_eq!(p.is_zero().unwrap_u8(), 1);
+        assert_eq!(p.ct_eq(&FieldElement::zero()).unwrap_u8(), 1);
+
+        // p + 1 decodes to one.
+        p_bytes[0] = 0xee;
+        let p_plus_1 = FieldElement::from_bytes(&p_bytes);
+        assert_eq!(p_plus_1.is_zero().unwrap_u8(), 0);
+        assert_eq!(p_plus_1.ct_eq(&FieldElement::one()).unwrap_u8(), 1);
+    }
+
     /// Notice that the last element has the high bit set, which
     /// should be ignored
     static B_BYTES: [u8;32] =
@@ -450,8 +697,102 @@ mod test {
         }
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn batch_invert_with_zeros() {
+        let a = FieldElement::from_bytes(&A_BYTES);
+        let ainv = FieldElement::from_bytes(&AINV_BYTES);
+        let asq = FieldElement::from_bytes(&ASQ_BYTES);
+        let zero = FieldElement::zero();
+
+        let mut list = vec![zero, a, zero, asq, zero];
+        FieldElement::batch_invert(&mut list[..]);
+
+        assert_eq!(list, vec![zero, ainv, zero, asq.invert(), zero]);
+        assert_eq!(zero.invert(), zero);
+    }
+
+    /// Run a fixed sequence of field operations on pseudorandom and
+    /// edge-case inputs, and hash every result.
+    ///
+    /// The expected digest is the same for every backend, so running
+    /// the tests with each of `u32_backend` and `u64_backend` checks
+    /// that the backends agree byte-for-byte.
+    #[test]
+    fn backend_independent_transcript() {
+        use digest::Digest;
+
+        // p - 1, p, p + 1 (unreduced), 2^255 - 1 (unreduced), and 2^254
+        let mut p_minus_one = [0xffu8; 32];
+        p_minus_one[0] = 0xec;
+        p_minus_one[31] = 0x7f;
+        let mut p = p_minus_one;
+        p[0] = 0xed;
+        let mut p_plus_one = p_minus_one;
+        p_plus_one[0] = 0xee;
+        let mut two_254 = [0u8; 32];
+        two_254[31] = 0x40;
+        let edge_cases = [p_minus_one, p, p_plus_one, [0xffu8; 32], two_254, [0u8; 32]];
+
+        let input = |i: u32| -> FieldElement {
+            let hash = sha2::Sha512::digest(&i.to_le_bytes());
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(&hash[..32]);
+            FieldElement::from_bytes(&bytes)
+        };
+
+        let mut transcript = sha2::Sha512::new();
+        let mut record = |x: &FieldElement| transcript.input(x.to_bytes());
+
+        let mut inputs = vec![];
+        inputs.extend(edge_cases.iter().map(FieldElement::from_bytes));
+        inputs.extend((0..250).map(input));
+
+        // Carry a running value through the loop, so that the inputs to
+        // later operations are outputs of earlier ones.
+        let mut acc = FieldElement::one();
+        for (i, pair) in inputs.windows(2).enumerate() {
+            let (a, b) = (&pair[0], &pair[1]);
+
+            record(&(a + b));
+            record(&(a - b));
+            record(&(a * b));
+            record(&a.square());
+            record(&a.square2());
+            record(&a.pow2k(1 + (i as u32 % 13)));
+            let mut neg_a = *a;
+            neg_a.negate();
+            record(&neg_a);
+            record(&a.invert());
+
+            let (was_square, r) = FieldElement::sqrt_ratio_i(a, b);
+            record(&r);
+            record(&FieldElement::from_u64(was_square.unwrap_u8() as u64));
+
+            acc = &(&acc * a).square() - &(&acc + b);
+            record(&acc);
+        }
+
+        let digest: ::std::string::String = transcript.result().iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(
+            digest,
+            "66684eaf127be867e1824c74d9086f85167a339ba1231656f89bef525cfc9cf7\
+             6c8515c8ca83f7e14b7fdd89b97e526836fcfe827e7ba04e700efa172502c9b3"
+        );
+    }
+
     #[test]
     fn batch_invert_empty() {
         FieldElement::batch_invert(&mut []);
     }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize_field_element() {
+        use zeroize::Zeroize;
+
+        let mut a = FieldElement::from_bytes(&A_BYTES);
+        a.zeroize();
+        assert_eq!(a, FieldElement::zero());
+    }
 }
@@ -0,0 +1,41 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Hex decoding of 32-byte values.
+//!
+//! The decoder is a `const fn`, so that it can be used to initialize
+//! `const`s from string literals.
+
+/// Decode a string of 64 hex digits (in either case) into 32 bytes.
+///
+/// Returns `None` if the string has the wrong length or contains a
+/// character which is not a hex digit.
+pub(crate) const fn decode(s: &str) -> Option<[u8; 32]> {
+    let s = s.as_bytes();
+    if s.len() != 64 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        match (digit(s[2 * i]), digit(s[2 * i + 1])) {
+            (Some(hi), Some(lo)) => bytes[i] = (hi << 4) | lo,
+            _ => return None,
+        }
+        i += 1;
+    }
+
+    Some(bytes)
+}
+
+const fn digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
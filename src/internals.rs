@@ -0,0 +1,62 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Internal curve models, for experimenting with point arithmetic.
+//!
+//! **This module is not covered by semver.**  It is only available
+//! with the `yolocrypto` feature, and its contents may change or be
+//! removed in any release.
+//!
+//! Internally, point arithmetic passes between several models of the
+//! curve: for instance, adding a `ProjectiveNielsPoint` to an
+//! `EdwardsPoint`, or doubling a `ProjectivePoint`, gives a
+//! `CompletedPoint`, which is then converted to whichever model the
+//! next operation needs.  This module exposes those models and their
+//! conversions, so that new formulas can be prototyped against them.
+//! The relationships between the models are described in the
+//! documentation of the `curve_models` module in the backend.
+//!
+//! The fields of these types are public, so unlike `EdwardsPoint`,
+//! they can hold values which are not points on the curve.
+//!
+//! # Example
+//!
+//! ```
+//! use curve25519_dalek::constants;
+//! use curve25519_dalek::internals;
+//!
+//! let B = constants::ED25519_BASEPOINT_POINT;
+//!
+//! // Compute 3B with one doubling and one addition, converting the
+//! // intermediate `CompletedPoint`s by hand.
+//! let B2 = internals::to_projective(&B).double().to_extended();
+//! let B3 = (&B2 + &internals::to_projective_niels(&B)).to_extended();
+//!
+//! assert_eq!(B3, B + B + B);
+//! ```
+
+pub use backend::serial::curve_models::{
+    AffineNielsPoint, CompletedPoint, ProjectiveNielsPoint, ProjectivePoint,
+};
+
+use edwards::EdwardsPoint;
+
+/// Convert `point` to the \\( \mathbb P\^2 \\) model, which is used
+/// for repeated doublings.
+pub fn to_projective(point: &EdwardsPoint) -> ProjectivePoint {
+    point.to_projective()
+}
+
+/// Convert `point` to projective Niels coordinates, the form used for
+/// the right-hand side of an addition.
+pub fn to_projective_niels(point: &EdwardsPoint) -> ProjectiveNielsPoint {
+    point.to_projective_niels()
+}
+
+/// Convert `point` to affine Niels coordinates, the form used for the
+/// right-hand side of a mixed addition.  This costs an inversion.
+pub fn to_affine_niels(point: &EdwardsPoint) -> AffineNielsPoint {
+    point.to_affine_niels()
+}
@@ -59,9 +59,20 @@ extern crate subtle;
 
 #[cfg(all(test, feature = "serde"))]
 extern crate bincode;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 #[cfg(feature = "serde")]
 extern crate serde;
 
+#[cfg(feature = "group")]
+extern crate group;
+#[cfg(feature = "group")]
+extern crate rand_core_06;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
 // Internal macros. Must come first!
 #[macro_use]
 pub(crate) mod macros;
@@ -88,18 +99,29 @@ pub mod constants;
 // External (and internal) traits.
 pub mod traits;
 
+// Finite field arithmetic mod p = 2^255 - 19
+pub mod field;
+
+// Constant-time lookup tables for windowed scalar multiplication
+pub mod window;
+
+// Internal curve models, exposed for experimentation (not covered by semver)
+#[cfg(feature = "yolocrypto")]
+pub mod internals;
+
 //------------------------------------------------------------------------
 // curve25519-dalek internal modules
 //------------------------------------------------------------------------
 
-// Finite field arithmetic mod p = 2^255 - 19
-pub(crate) mod field;
-
 // Arithmetic backends (using u32, u64, etc) live here
 pub(crate) mod backend;
 
 // Crate-local prelude (for alloc-dependent features like `Vec`)
 pub(crate) mod prelude;
 
-// Generic code for window lookups
-pub(crate) mod window;
+// Hex decoding, shared by `from_hex` constructors and the serde encodings
+pub(crate) mod hex;
+
+// Shared helpers for the serde encodings
+#[cfg(feature = "serde")]
+pub(crate) mod serde_hex;
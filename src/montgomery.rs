@@ -40,7 +40,18 @@
 //! This function rejects `MontgomeryPoints` which correspond to points
 //! on the twist.
 //!
+//! # X25519
+//!
+//! The `x25519` function implements the X25519 function of [RFC
+//! 7748][rfc7748] on byte strings, including the clamping of the
+//! secret key.  Public keys are computed as
+//! `x25519(secret, constants::X25519_BASEPOINT.to_bytes())`, or more
+//! quickly with `MontgomeryPoint::mul_base_clamped`.  Protocols which
+//! require contributory behaviour should use `x25519_contributory`,
+//! which rejects an all-zero shared secret.
+//!
 //! [costello-smith]: https://eprint.iacr.org/2017/212.pdf
+//! [rfc7748]: https://tools.ietf.org/html/rfc7748#section-5
 
 // We allow non snake_case names because coordinates in projective space are
 // traditionally denoted by the capitalisation of their respective
@@ -50,7 +61,7 @@
 
 use core::ops::{Mul, MulAssign};
 
-use constants::APLUS2_OVER_FOUR;
+use constants::{APLUS2_OVER_FOUR, MONTGOMERY_A, MONTGOMERY_A_NEG};
 use edwards::{CompressedEdwardsY, EdwardsPoint};
 use field::FieldElement;
 use scalar::Scalar;
@@ -58,6 +69,7 @@ use scalar::Scalar;
 use traits::Identity;
 
 use subtle::Choice;
+use subtle::ConditionallyNegatable;
 use subtle::ConditionallySelectable;
 use subtle::ConstantTimeEq;
 
@@ -90,6 +102,56 @@ impl PartialEq for MontgomeryPoint {
 
 impl Eq for MontgomeryPoint {}
 
+// ------------------------------------------------------------------------
+// Serde support
+// ------------------------------------------------------------------------
+// Serializes to and from the 32 bytes of the u-coordinate.
+
+#[cfg(feature = "serde")]
+use serde::{self, Serialize, Deserialize, Serializer, Deserializer};
+#[cfg(feature = "serde")]
+use serde::de::Visitor;
+
+#[cfg(feature = "serde")]
+impl Serialize for MontgomeryPoint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_bytes(self.as_bytes())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for MontgomeryPoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        struct MontgomeryPointVisitor;
+
+        impl<'de> Visitor<'de> for MontgomeryPointVisitor {
+            type Value = MontgomeryPoint;
+
+            fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                formatter.write_str("32 bytes of data")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<MontgomeryPoint, E>
+                where E: serde::de::Error
+            {
+                if v.len() == 32 {
+                    let mut arr32 = [0u8; 32];
+                    arr32[0..32].copy_from_slice(v);
+                    Ok(MontgomeryPoint(arr32))
+                } else {
+                    Err(serde::de::Error::invalid_length(v.len(), &self))
+                }
+            }
+        }
+
+        deserializer.deserialize_bytes(MontgomeryPointVisitor)
+    }
+}
+
 impl MontgomeryPoint {
     /// View this `MontgomeryPoint` as an array of bytes.
     pub fn as_bytes<'a>(&'a self) -> &'a [u8; 32] {
@@ -101,6 +163,44 @@ impl MontgomeryPoint {
         self.0
     }
 
+    /// Determine in constant time whether this is the \\(u\\)-coordinate
+    /// of a point of small order, on either the curve or its twist.
+    ///
+    /// The bytes are decoded as in X25519: the high bit is ignored, and
+    /// unreduced values are reduced modulo \\(p\\).  A point has small
+    /// order if its order divides \\(8\\), so that multiplying it by
+    /// any clamped scalar gives \\(u = 0\\).  With this decoding, these
+    /// are exactly the values in the blocklist of [RFC 7748 Section
+    /// 7][rfc7748]: \\(0\\), \\(1\\), \\(-1\\), the two \\(u\\)-coordinates of
+    /// points of order \\(8\\), and the unreduced encodings
+    /// \\(p\\) and \\(p + 1\\).
+    ///
+    /// [rfc7748]: https://tools.ietf.org/html/rfc7748#section-7
+    pub fn is_small_order(&self) -> Choice {
+        let u = FieldElement::from_bytes(&self.0);
+        let P = ProjectivePoint {
+            U: u,
+            W: FieldElement::one(),
+        };
+
+        // [8]P is the point at infinity, which has W = 0
+        P.double().double().double().W.is_zero()
+    }
+
+    /// Determine in constant time whether this is the \\(u\\)-coordinate
+    /// of a point on the curve, rather than on its twist.
+    ///
+    /// The bytes are decoded as in X25519: the high bit is ignored, and
+    /// unreduced values are reduced modulo \\(p\\).  The point is on the
+    /// curve exactly when \\(u\^3 + Au\^2 + u\\) is a square (possibly
+    /// zero), and on the twist otherwise.
+    pub fn is_on_curve(&self) -> Choice {
+        let u = FieldElement::from_bytes(&self.0);
+        let uu = u.square();
+        let v_squared = &u * &(&(&uu + &(&MONTGOMERY_A * &u)) + &FieldElement::one());
+        v_squared.is_square()
+    }
+
     /// Attempt to convert to an `EdwardsPoint`, using the supplied
     /// choice of sign for the `EdwardsPoint`.
     ///
@@ -112,6 +212,17 @@ impl MontgomeryPoint {
     /// * `None` if `self` is the \\(u\\)-coordinate of a point on the
     /// twist of (the Montgomery form of) Curve25519;
     ///
+    /// # Exceptional points
+    ///
+    /// The birational map \\(y = (u-1)/(u+1)\\) is undefined at
+    /// \\(u = -1\\).  Since \\(u = -1\\) is the \\(u\\)-coordinate of
+    /// a point on the twist, not the curve, this returns `None`.
+    ///
+    /// The \\(u\\)-coordinate \\(u = 0\\) is sent to the 2-torsion
+    /// point \\((0,-1)\\) of the Edwards curve, for either choice of
+    /// `sign`.  Note that `EdwardsPoint::to_montgomery` sends both
+    /// the identity and \\((0,-1)\\) to \\(u = 0\\), so the identity
+    /// does not survive a round trip through the Montgomery model.
     pub fn to_edwards(&self, sign: u8) -> Option<EdwardsPoint> {
         // To decompress the Montgomery u coordinate to an
         // `EdwardsPoint`, we apply the birational map to obtain the
@@ -140,6 +251,290 @@ impl MontgomeryPoint {
 
         CompressedEdwardsY(y_bytes).decompress()
     }
+
+    /// Attempt to convert to both of the `EdwardsPoint`s with this
+    /// \\(u\\)-coordinate, for protocols which don't know the sign.
+    ///
+    /// # Return
+    ///
+    /// * `Some((P, -P))` if `self` is the \\(u\\)-coordinate of a point
+    ///   on (the Montgomery form of) Curve25519, where `P` is
+    ///   `to_edwards(0)`, with nonnegative \\(x\\)-coordinate, and `-P`
+    ///   is `to_edwards(1)`;
+    ///
+    /// * `None` if `self` is the \\(u\\)-coordinate of a point on the
+    ///   twist of (the Montgomery form of) Curve25519, including the
+    ///   exceptional point \\(u = -1\\).
+    ///
+    /// As for `to_edwards`, \\(u = 0\\) is sent to the 2-torsion point
+    /// \\((0,-1)\\), which is its own negation, so both points are
+    /// the same.  The identity is never returned.
+    pub fn to_edwards_both(&self) -> Option<(EdwardsPoint, EdwardsPoint)> {
+        let P = self.to_edwards(0)?;
+        Some((P, -P))
+    }
+
+    /// Multiply the point with \\(u\\)-coordinate `self` and Edwards
+    /// sign `sign` by `scalar`, returning both the \\(u\\)-coordinate
+    /// and the Edwards sign of the result.
+    ///
+    /// The input point \\(P\\) is the one given by `to_edwards(sign)`,
+    /// and the output is \\((u([n]P), s)\\), where \\(s\\) is the
+    /// sign of the \\(x\\)-coordinate of \\([n]P\\) on the Edwards
+    /// curve, so that `u.to_edwards(s.unwrap_u8())` is \\([n]P\\).
+    ///
+    /// This runs the usual Montgomery ladder, which also produces
+    /// \\(u([n+1]P)\\), and recovers the \\(v\\)-coordinate of
+    /// \\([n]P\\) (and hence the sign) with the formula of Okeya and
+    /// Sakurai, rather than doing a separate decompression.
+    ///
+    /// The input point is `self` together with `sign`, rather than a
+    /// separate base point, and the result is an `Option`, unlike a
+    /// plain `scalar * point`: a \\(u\\)-coordinate alone determines
+    /// \\(P\\) only up to sign, so the sign of \\([n]P\\) is
+    /// only meaningful once the sign of \\(P\\) is fixed, and a
+    /// \\(u\\)-coordinate on the twist has no Edwards sign at all.
+    ///
+    /// # Return
+    ///
+    /// * `Some((u, sign))` if `self` is the \\(u\\)-coordinate of a
+    ///   point on Curve25519;
+    ///
+    /// * `None` if `self` is the \\(u\\)-coordinate of a point on the
+    ///   twist, as for `to_edwards`.
+    ///
+    /// # Exceptional points
+    ///
+    /// The sign is \\(0\\) whenever the \\(x\\)-coordinate of
+    /// \\([n]P\\) is zero, that is, when \\([n]P\\) is the identity
+    /// or the 2-torsion point.  If \\(P\\) itself is the 2-torsion
+    /// point \\(u = 0\\), the sign carries no information about
+    /// \\(P\\), and \\([n]P\\) always has sign \\(0\\).
+    pub fn mul_with_sign(&self, sign: u8, scalar: &Scalar) -> Option<(MontgomeryPoint, Choice)> {
+        // Decompress P to get its Edwards x-coordinate, which fixes
+        // the sign of its Montgomery v-coordinate.  Decompression
+        // gives Z = 1, so P.X is the affine x.
+        let P = self.to_edwards(sign)?;
+        let x_P = P.X;
+        let u_P = FieldElement::from_bytes(&self.0);
+
+        let (Q, R) = montgomery_ladder(&u_P, scalar);
+        let u_Q = &Q.U * &Q.W.invert();
+        let u_R = &R.U * &R.W.invert();
+
+        // Okeya-Sakurai: with Q = [n]P and R = Q + P,
+        //
+        //    2 v_P v_Q = (u_P u_Q + 1)(u_P + u_Q + 2A) - 2A - (u_P - u_Q)^2 u_R.
+        //
+        // The Edwards x-coordinate is x = c u / v, with c^2 = -(A + 2),
+        // so v_P = c u_P / x_P, and
+        //
+        //    x_Q = c u_Q / v_Q = -2 (A + 2) u_P u_Q / (x_P N),
+        //
+        // where N is the right-hand side above.  This does not depend
+        // on the choice of c.
+        let one = FieldElement::one();
+        let two_A = &MONTGOMERY_A + &MONTGOMERY_A;
+        let u_P_minus_u_Q = &u_P - &u_Q;
+        let N = &(&(&(&(&u_P * &u_Q) + &one) * &(&(&u_P + &u_Q) + &two_A)) - &two_A)
+            - &(&u_P_minus_u_Q.square() * &u_R);
+
+        let two = &one + &one;
+        let two_A_plus_four = &(&two_A + &two) + &two;
+        let num = -&(&(&two_A_plus_four * &u_P) * &u_Q);
+        let mut x_Q = &num * &(&x_P * &N).invert();
+
+        // When R is the identity, Q = -P, and the formula above does
+        // not apply.
+        x_Q.conditional_assign(&(-&x_P), R.W.is_zero());
+
+        Some((MontgomeryPoint(u_Q.to_bytes()), x_Q.is_negative()))
+    }
+
+    /// Map a field element to a point on Curve25519 using the
+    /// Elligator 2 map.
+    ///
+    /// This is the map described in section 5 of [_Elligator:
+    /// Elliptic-curve points indistinguishable from uniform random
+    /// strings_][elligator] by Bernstein, Hamburg, Krasnova, and
+    /// Lange, with the nonsquare \\(2\\).  The output is always the
+    /// \\(u\\)-coordinate of a point on the curve (never on the
+    /// twist), and \\(r\\) and \\(-r\\) have the same image.
+    ///
+    /// The output is not uniformly distributed over the curve, but
+    /// the sum of the images of two independent uniformly random
+    /// field elements is statistically close to uniform.
+    ///
+    /// This function runs in constant time.
+    ///
+    /// [elligator]: https://elligator.cr.yp.to/elligator-20130828.pdf
+    pub fn elligator_encode(r_0: &FieldElement) -> MontgomeryPoint {
+        let one = FieldElement::one();
+
+        // d = -A/(1+2r^2)
+        let d_1 = &one + &r_0.square2();
+        let d = &MONTGOMERY_A_NEG * &d_1.invert();
+
+        // eps = d^3 + Ad^2 + d
+        let d_sq = d.square();
+        let au = &MONTGOMERY_A * &d;
+        let inner = &(&d_sq + &au) + &one;
+        let eps = &d * &inner;
+
+        // If eps is square, u = d.  Otherwise, u = -d - A.
+        let (eps_is_sq, _) = FieldElement::sqrt_ratio_i(&eps, &one);
+        let A_or_zero = FieldElement::conditional_select(&MONTGOMERY_A, &FieldElement::zero(), eps_is_sq);
+        let mut u = &d + &A_or_zero;
+        u.conditional_negate(!eps_is_sq);
+
+        MontgomeryPoint(u.to_bytes())
+    }
+
+    /// Multiply this `MontgomeryPoint` by the clamped integer
+    /// obtained from `bytes`, as specified for X25519.
+    ///
+    /// Following [RFC 7748][rfc7748], the three lowest bits and the
+    /// highest bit of `bytes` are cleared and the second-highest bit
+    /// is set before the Montgomery ladder is run, so `bytes` can be
+    /// a uniformly random secret key.
+    ///
+    /// The result is the X25519 function applied to `bytes` and
+    /// `self`.
+    ///
+    /// [rfc7748]: https://tools.ietf.org/html/rfc7748#section-5
+    pub fn mul_clamped(self, bytes: [u8; 32]) -> MontgomeryPoint {
+        // The clamped integer is less than 2^255 but not reduced mod
+        // l, which is what the ladder needs, since `self` need not
+        // lie in the prime-order subgroup.
+        let s = Scalar::from_bits_clamped(bytes);
+        self * s
+    }
+
+    /// Multiply the X25519 basepoint \\(u = 9\\) by the clamped
+    /// integer obtained from `bytes`, as specified for X25519.
+    ///
+    /// This is equivalent to
+    /// `constants::X25519_BASEPOINT.mul_clamped(bytes)`, that is, it
+    /// computes the X25519 public key for the secret key `bytes`, but
+    /// uses the precomputed Ed25519 basepoint table.
+    #[cfg(feature = "stage2_build")]
+    pub fn mul_base_clamped(bytes: [u8; 32]) -> MontgomeryPoint {
+        use constants::ED25519_BASEPOINT_TABLE;
+
+        // The clamped integer is less than 2^255, so it can be used
+        // directly with the basepoint table.
+        let s = Scalar::from_bits_clamped(bytes);
+        (&ED25519_BASEPOINT_TABLE * &s).to_montgomery()
+    }
+
+    /// Given `self` \\( = u(P) \\), return \\( u([2]P) \\).
+    ///
+    /// This is the doubling step of the Montgomery ladder, exposed
+    /// for building custom ladders together with `differential_add`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants::X25519_BASEPOINT;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// assert_eq!(X25519_BASEPOINT.double(), X25519_BASEPOINT * Scalar::from(2u64));
+    /// ```
+    pub fn double(&self) -> MontgomeryPoint {
+        ProjectivePoint {
+            U: FieldElement::from_bytes(&self.0),
+            W: FieldElement::one(),
+        }.double().to_affine()
+    }
+
+    /// Given `self` \\( = u(P) \\), `q` \\( = u(Q) \\), and
+    /// `p_minus_q` \\( = u(P - Q) \\), return \\( u(P + Q) \\).
+    ///
+    /// This is the differential addition step of the Montgomery
+    /// ladder.  Since the \\(u\\)-coordinate does not determine the
+    /// sign of a point, \\(u(P + Q)\\) cannot be computed from
+    /// \\(u(P)\\) and \\(u(Q)\\) alone: the caller must supply the
+    /// correct difference, and if `p_minus_q` is not \\(u(P - Q)\\)
+    /// the result is meaningless.
+    ///
+    /// If `p_minus_q` is \\(0\\), for instance when \\(P = Q\\), the
+    /// result is \\(0\\); use `double` to compute \\(u([2]P)\\).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants::X25519_BASEPOINT;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let P = X25519_BASEPOINT * Scalar::from(5u64);
+    /// let Q = X25519_BASEPOINT * Scalar::from(3u64);
+    /// let P_minus_Q = X25519_BASEPOINT * Scalar::from(2u64);
+    ///
+    /// assert_eq!(P.differential_add(&Q, &P_minus_Q), X25519_BASEPOINT * Scalar::from(8u64));
+    /// ```
+    pub fn differential_add(&self, q: &MontgomeryPoint, p_minus_q: &MontgomeryPoint) -> MontgomeryPoint {
+        let u_P = FieldElement::from_bytes(&self.0);
+        let u_Q = FieldElement::from_bytes(&q.0);
+        let u_PmQ = FieldElement::from_bytes(&p_minus_q.0);
+
+        // As in `differential_add_and_double`, with W_P = W_Q = 1
+        let t7 = &(&u_P + &FieldElement::one()) * &(&u_Q - &FieldElement::one());
+        let t8 = &(&u_P - &FieldElement::one()) * &(&u_Q + &FieldElement::one());
+
+        ProjectivePoint {
+            U: (&t7 + &t8).square(),           // 4 (u_P u_Q - 1)^2
+            W: &u_PmQ * &(&t7 - &t8).square(), // u_{P-Q} 4 (u_Q - u_P)^2
+        }.to_affine()
+    }
+}
+
+/// The X25519 function of RFC 7748: multiply the point with
+/// \\(u\\)-coordinate `public` by the clamped integer obtained from
+/// `secret`, and return the encoding of the result.
+///
+/// This is the same as `MontgomeryPoint(public).mul_clamped(secret)`.
+/// As specified by RFC 7748, the high bit of `public` is ignored, and
+/// no check is made on the output; see `x25519_contributory`.
+///
+/// # Example
+///
+/// ```
+/// use curve25519_dalek::constants::X25519_BASEPOINT;
+/// use curve25519_dalek::montgomery::x25519;
+///
+/// let alice_secret = [0x11u8; 32];
+/// let bob_secret = [0x22u8; 32];
+///
+/// let alice_public = x25519(alice_secret, X25519_BASEPOINT.to_bytes());
+/// let bob_public = x25519(bob_secret, X25519_BASEPOINT.to_bytes());
+///
+/// assert_eq!(x25519(alice_secret, bob_public), x25519(bob_secret, alice_public));
+/// ```
+pub fn x25519(secret: [u8; 32], public: [u8; 32]) -> [u8; 32] {
+    MontgomeryPoint(public).mul_clamped(secret).to_bytes()
+}
+
+/// The X25519 function, rejecting an all-zero output.
+///
+/// The output is zero exactly when `public` is a point of small order,
+/// in which case the shared secret does not depend on `secret`.
+/// Protocols which require both parties to contribute to the shared
+/// secret should use this function rather than `x25519`.
+///
+/// # Return
+///
+/// * `Some(shared)`, where `shared` is `x25519(secret, public)`, if it
+///   is nonzero;
+///
+/// * `None` otherwise.
+pub fn x25519_contributory(secret: [u8; 32], public: [u8; 32]) -> Option<[u8; 32]> {
+    let shared = x25519(secret, public);
+
+    if shared.ct_eq(&[0u8; 32]).unwrap_u8() == 1 {
+        None
+    } else {
+        Some(shared)
+    }
 }
 
 /// A `ProjectivePoint` holds a point on the projective line
@@ -190,6 +585,19 @@ impl ProjectivePoint {
         let u = &self.U * &self.W.invert();
         MontgomeryPoint(u.to_bytes())
     }
+
+    /// Compute \\( u([2]P) \\), as in the doubling half of
+    /// `differential_add_and_double`.
+    fn double(&self) -> ProjectivePoint {
+        let t0 = (&self.U + &self.W).square(); // (U + W)^2
+        let t1 = (&self.U - &self.W).square(); // (U - W)^2
+        let t2 = &t0 - &t1;                    // 4 U W
+
+        ProjectivePoint {
+            U: &t0 * &t1,
+            W: &t2 * &(&t1 + &(&APLUS2_OVER_FOUR * &t2)),
+        }
+    }
 }
 
 /// Perform the double-and-add step of the Montgomery ladder.
@@ -257,28 +665,37 @@ impl<'a, 'b> Mul<&'b Scalar> for &'a MontgomeryPoint {
 
     /// Given `self` \\( = u\_0(P) \\), and a `Scalar` \\(n\\), return \\( u\_0([n]P) \\).
     fn mul(self, scalar: &'b Scalar) -> MontgomeryPoint {
-        // Algorithm 8 of Costello-Smith 2017
         let affine_u = FieldElement::from_bytes(&self.0);
-        let mut x0 = ProjectivePoint::identity();
-        let mut x1 = ProjectivePoint {
-            U: affine_u,
-            W: FieldElement::one(),
-        };
+        let (x0, _) = montgomery_ladder(&affine_u, scalar);
 
-        let bits: [i8; 256] = scalar.bits();
+        x0.to_affine()
+    }
+}
 
-        for i in (0..255).rev() {
-            let choice: u8 = (bits[i + 1] ^ bits[i]) as u8;
+/// Run the Montgomery ladder on the \\(u\\)-coordinate `affine_u` of a
+/// point \\(P\\), returning \\( (u([n]P), u([n+1]P)) \\) in projective
+/// coordinates.
+fn montgomery_ladder(affine_u: &FieldElement, scalar: &Scalar) -> (ProjectivePoint, ProjectivePoint) {
+    // Algorithm 8 of Costello-Smith 2017
+    let mut x0 = ProjectivePoint::identity();
+    let mut x1 = ProjectivePoint {
+        U: *affine_u,
+        W: FieldElement::one(),
+    };
 
-            debug_assert!(choice == 0 || choice == 1);
+    let bits: [i8; 256] = scalar.bit_array();
 
-            ProjectivePoint::conditional_swap(&mut x0, &mut x1, choice.into());
-            differential_add_and_double(&mut x0, &mut x1, &affine_u);
-        }
-        ProjectivePoint::conditional_swap(&mut x0, &mut x1, Choice::from(bits[0] as u8));
+    for i in (0..255).rev() {
+        let choice: u8 = (bits[i + 1] ^ bits[i]) as u8;
 
-        x0.to_affine()
+        debug_assert!(choice == 0 || choice == 1);
+
+        ProjectivePoint::conditional_swap(&mut x0, &mut x1, choice.into());
+        differential_add_and_double(&mut x0, &mut x1, affine_u);
     }
+    ProjectivePoint::conditional_swap(&mut x0, &mut x1, Choice::from(bits[0] as u8));
+
+    (x0, x1)
 }
 
 impl<'b> MulAssign<&'b Scalar> for MontgomeryPoint {
@@ -322,6 +739,111 @@ mod test {
         );
     }
 
+    #[test]
+    fn montgomery_double_and_differential_add() {
+        let B = constants::X25519_BASEPOINT;
+        let a = Scalar::from(8475983829u64).invert();
+        let b = Scalar::from(5464563u64).invert();
+        let P = B * a;
+        let Q = B * b;
+
+        assert_eq!(P.double(), B * (a + a));
+        assert_eq!(P.differential_add(&Q, &(B * (a - b))), B * (a + b));
+        assert_eq!(Q.differential_add(&P, &(B * (b - a))), B * (a + b));
+        assert_eq!(P.differential_add(&P, &MontgomeryPoint([0u8; 32])), MontgomeryPoint([0u8; 32]));
+
+        // A three-step ladder for 13 = 0b1101, keeping (nP, (n+1)P)
+        let mut x0 = B;
+        let mut x1 = B.double();
+        for bit in [1, 0, 1].iter() {
+            if *bit == 1 {
+                x0 = x0.differential_add(&x1, &B);
+                x1 = x1.double();
+            } else {
+                x1 = x1.differential_add(&x0, &B);
+                x0 = x0.double();
+            }
+        }
+        assert_eq!(x0, B * Scalar::from(13u64));
+        assert_eq!(x1, B * Scalar::from(14u64));
+    }
+
+    /// Elligator 2 image of the field element with bytes 0, 1, ..., 31
+    static ELLIGATOR_ENCODE_0_TO_31: [u8; 32] = [
+        0x5f, 0x35, 0x20, 0x00, 0x1c, 0x6c, 0x99, 0x36,
+        0xa3, 0x12, 0x06, 0xaf, 0xe7, 0xc7, 0xac, 0x22,
+        0x4e, 0x88, 0x61, 0x61, 0x9b, 0xf9, 0x88, 0x72,
+        0x44, 0x49, 0x15, 0x89, 0x9d, 0x95, 0xf4, 0x6e];
+
+    #[test]
+    fn elligator_encode_known_answer() {
+        let mut bytes = [0u8; 32];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let u = MontgomeryPoint::elligator_encode(&FieldElement::from_bytes(&bytes));
+        assert_eq!(u.to_bytes(), ELLIGATOR_ENCODE_0_TO_31);
+
+        let zero = MontgomeryPoint::elligator_encode(&FieldElement::zero());
+        assert_eq!(zero.to_bytes(), [0u8; 32]);
+    }
+
+    #[test]
+    fn elligator_encode_lands_on_curve() {
+        let mut r = FieldElement::one();
+        for _ in 0..100 {
+            let u = MontgomeryPoint::elligator_encode(&r);
+            assert!(u.to_edwards(0).is_some());
+            // r and -r have the same image
+            assert_eq!(u, MontgomeryPoint::elligator_encode(&-&r));
+            r = &r.square() + &constants::MONTGOMERY_A;
+        }
+    }
+
+    /// Test the exceptional points u = -1 and u = 0 in Montgomery -> Edwards
+    #[test]
+    fn montgomery_to_edwards_exceptional_points() {
+        let minus_one = MontgomeryPoint(FieldElement::minus_one().to_bytes());
+        assert!(minus_one.to_edwards(0).is_none());
+        assert!(minus_one.to_edwards(1).is_none());
+
+        // u = 0 is the image of the 2-torsion point (0,-1)
+        let two_torsion = constants::EIGHT_TORSION[4];
+        let zero = MontgomeryPoint([0u8; 32]);
+        assert_eq!(zero.to_edwards(0).unwrap(), two_torsion);
+        assert_eq!(zero.to_edwards(1).unwrap(), two_torsion);
+        assert_eq!(two_torsion.to_montgomery(), zero);
+        assert_eq!(EdwardsPoint::identity().to_montgomery(), zero);
+    }
+
+    #[test]
+    fn montgomery_to_edwards_both() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let (P, Q) = constants::X25519_BASEPOINT.to_edwards_both().unwrap();
+        assert_eq!(P, B);
+        assert_eq!(Q, -B);
+        assert_eq!(Q.compress(), constants::X25519_BASEPOINT.to_edwards(1).unwrap().compress());
+
+        // Each torsion point other than the identity is one of the
+        // preimages of its u-coordinate; u = 0 has the single preimage
+        // (0,-1), and the identity is not a preimage.
+        for T in constants::EIGHT_TORSION[1..].iter() {
+            let (P, Q) = T.to_montgomery().to_edwards_both().unwrap();
+            assert!(P == *T || Q == *T);
+            assert_eq!(P, -Q);
+        }
+        let (P, Q) = MontgomeryPoint([0u8; 32]).to_edwards_both().unwrap();
+        assert_eq!(P, constants::EIGHT_TORSION[4]);
+        assert_eq!(Q, constants::EIGHT_TORSION[4]);
+
+        // Points on the twist, including the exceptional point u = -1
+        let mut two = [0u8; 32];
+        two[0] = 2;
+        assert!(MontgomeryPoint(two).to_edwards_both().is_none());
+        let minus_one = MontgomeryPoint(FieldElement::minus_one().to_bytes());
+        assert!(minus_one.to_edwards_both().is_none());
+    }
+
     /// Test Edwards -> Montgomery on the X/Ed25519 basepoint
     #[test]
     fn basepoint_edwards_to_montgomery() {
@@ -372,4 +894,255 @@ mod test {
 
         assert_eq!(result, expected.to_montgomery())
     }
+
+    #[test]
+    fn mul_with_sign_matches_edwards_scalarmult() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let P1 = B * Scalar::from(3u64).invert();
+        let P2 = B * Scalar::from(11u64).invert();
+        let points = [
+            B,
+            P1,
+            -P1,
+            P2 + constants::EIGHT_TORSION[1],
+            P2 + constants::EIGHT_TORSION[6],
+        ];
+
+        let scalars = [
+            Scalar::from(5u64).invert(),
+            Scalar::from(7u64).invert(),
+            Scalar::one(),
+            Scalar::from(2u64),
+            Scalar::zero(),
+            // [l-1]P = -P for P in the prime-order subgroup
+            -Scalar::one(),
+            // [l]P is a torsion point, or the identity
+            constants::BASEPOINT_ORDER,
+        ];
+
+        for P in points.iter() {
+            let u = P.to_montgomery();
+            let sign = P.compress().as_bytes()[31] >> 7;
+            assert_eq!(u.to_edwards(sign), Some(*P));
+
+            for s in scalars.iter() {
+                let expected = P * s;
+                let (result, result_sign) = u.mul_with_sign(sign, s).unwrap();
+                assert_eq!(result, expected.to_montgomery());
+                assert_eq!(result_sign.unwrap_u8(), expected.compress().as_bytes()[31] >> 7);
+            }
+        }
+    }
+
+    #[test]
+    fn mul_with_sign_exceptional_points() {
+        // The twist point u = -1 is rejected
+        let minus_one = MontgomeryPoint(FieldElement::minus_one().to_bytes());
+        assert!(minus_one.mul_with_sign(0, &Scalar::one()).is_none());
+
+        // The 2-torsion point u = 0 always gives sign 0
+        let zero = MontgomeryPoint([0u8; 32]);
+        for s in [Scalar::zero(), Scalar::one(), Scalar::from(3u64)].iter() {
+            let (u, sign) = zero.mul_with_sign(1, s).unwrap();
+            assert_eq!(u, zero * s);
+            assert_eq!(sign.unwrap_u8(), 0);
+        }
+    }
+
+    /// X25519 test vectors from RFC 7748, section 5.2
+    #[test]
+    fn mul_clamped_rfc7748_vectors() {
+        let scalar: [u8; 32] = [
+            0xa5, 0x46, 0xe3, 0x6b, 0xf0, 0x52, 0x7c, 0x9d,
+            0x3b, 0x16, 0x15, 0x4b, 0x82, 0x46, 0x5e, 0xdd,
+            0x62, 0x14, 0x4c, 0x0a, 0xc1, 0xfc, 0x5a, 0x18,
+            0x50, 0x6a, 0x22, 0x44, 0xba, 0x44, 0x9a, 0xc4];
+        let u = MontgomeryPoint([
+            0xe6, 0xdb, 0x68, 0x67, 0x58, 0x30, 0x30, 0xdb,
+            0x35, 0x94, 0xc1, 0xa4, 0x24, 0xb1, 0x5f, 0x7c,
+            0x72, 0x66, 0x24, 0xec, 0x26, 0xb3, 0x35, 0x3b,
+            0x10, 0xa9, 0x03, 0xa6, 0xd0, 0xab, 0x1c, 0x4c]);
+        let expected: [u8; 32] = [
+            0xc3, 0xda, 0x55, 0x37, 0x9d, 0xe9, 0xc6, 0x90,
+            0x8e, 0x94, 0xea, 0x4d, 0xf2, 0x8d, 0x08, 0x4f,
+            0x32, 0xec, 0xcf, 0x03, 0x49, 0x1c, 0x71, 0xf7,
+            0x54, 0xb4, 0x07, 0x55, 0x77, 0xa2, 0x85, 0x52];
+        assert_eq!(u.mul_clamped(scalar).to_bytes(), expected);
+
+        let scalar: [u8; 32] = [
+            0x4b, 0x66, 0xe9, 0xd4, 0xd1, 0xb4, 0x67, 0x3c,
+            0x5a, 0xd2, 0x26, 0x91, 0x95, 0x7d, 0x6a, 0xf5,
+            0xc1, 0x1b, 0x64, 0x21, 0xe0, 0xea, 0x01, 0xd4,
+            0x2c, 0xa4, 0x16, 0x9e, 0x79, 0x18, 0xba, 0x0d];
+        let u = MontgomeryPoint([
+            0xe5, 0x21, 0x0f, 0x12, 0x78, 0x68, 0x11, 0xd3,
+            0xf4, 0xb7, 0x95, 0x9d, 0x05, 0x38, 0xae, 0x2c,
+            0x31, 0xdb, 0xe7, 0x10, 0x6f, 0xc0, 0x3c, 0x3e,
+            0xfc, 0x4c, 0xd5, 0x49, 0xc7, 0x15, 0xa4, 0x93]);
+        let expected: [u8; 32] = [
+            0x95, 0xcb, 0xde, 0x94, 0x76, 0xe8, 0x90, 0x7d,
+            0x7a, 0xad, 0xe4, 0x5c, 0xb4, 0xb8, 0x73, 0xf8,
+            0x8b, 0x59, 0x5a, 0x68, 0x79, 0x9f, 0xa1, 0x52,
+            0xe6, 0xf8, 0xf7, 0x64, 0x7a, 0xac, 0x79, 0x57];
+        assert_eq!(u.mul_clamped(scalar).to_bytes(), expected);
+    }
+
+    /// Diffie-Hellman test vectors from RFC 7748, section 6.1
+    #[test]
+    fn mul_base_clamped_rfc7748_diffie_hellman() {
+        let alice_secret: [u8; 32] = [
+            0x77, 0x07, 0x6d, 0x0a, 0x73, 0x18, 0xa5, 0x7d,
+            0x3c, 0x16, 0xc1, 0x72, 0x51, 0xb2, 0x66, 0x45,
+            0xdf, 0x4c, 0x2f, 0x87, 0xeb, 0xc0, 0x99, 0x2a,
+            0xb1, 0x77, 0xfb, 0xa5, 0x1d, 0xb9, 0x2c, 0x2a];
+        let alice_public: [u8; 32] = [
+            0x85, 0x20, 0xf0, 0x09, 0x89, 0x30, 0xa7, 0x54,
+            0x74, 0x8b, 0x7d, 0xdc, 0xb4, 0x3e, 0xf7, 0x5a,
+            0x0d, 0xbf, 0x3a, 0x0d, 0x26, 0x38, 0x1a, 0xf4,
+            0xeb, 0xa4, 0xa9, 0x8e, 0xaa, 0x9b, 0x4e, 0x6a];
+        let bob_secret: [u8; 32] = [
+            0x5d, 0xab, 0x08, 0x7e, 0x62, 0x4a, 0x8a, 0x4b,
+            0x79, 0xe1, 0x7f, 0x8b, 0x83, 0x80, 0x0e, 0xe6,
+            0x6f, 0x3b, 0xb1, 0x29, 0x26, 0x18, 0xb6, 0xfd,
+            0x1c, 0x2f, 0x8b, 0x27, 0xff, 0x88, 0xe0, 0xeb];
+        let bob_public: [u8; 32] = [
+            0xde, 0x9e, 0xdb, 0x7d, 0x7b, 0x7d, 0xc1, 0xb4,
+            0xd3, 0x5b, 0x61, 0xc2, 0xec, 0xe4, 0x35, 0x37,
+            0x3f, 0x83, 0x43, 0xc8, 0x5b, 0x78, 0x67, 0x4d,
+            0xad, 0xfc, 0x7e, 0x14, 0x6f, 0x88, 0x2b, 0x4f];
+        let shared_secret: [u8; 32] = [
+            0x4a, 0x5d, 0x9d, 0x5b, 0xa4, 0xce, 0x2d, 0xe1,
+            0x72, 0x8e, 0x3b, 0xf4, 0x80, 0x35, 0x0f, 0x25,
+            0xe0, 0x7e, 0x21, 0xc9, 0x47, 0xd1, 0x9e, 0x33,
+            0x76, 0xf0, 0x9b, 0x3c, 0x1e, 0x16, 0x17, 0x42];
+
+        assert_eq!(MontgomeryPoint::mul_base_clamped(alice_secret).to_bytes(), alice_public);
+        assert_eq!(MontgomeryPoint::mul_base_clamped(bob_secret).to_bytes(), bob_public);
+        assert_eq!(constants::X25519_BASEPOINT.mul_clamped(alice_secret).to_bytes(), alice_public);
+
+        let alice_shared = MontgomeryPoint(bob_public).mul_clamped(alice_secret);
+        let bob_shared = MontgomeryPoint(alice_public).mul_clamped(bob_secret);
+        assert_eq!(alice_shared.to_bytes(), shared_secret);
+        assert_eq!(bob_shared.to_bytes(), shared_secret);
+    }
+
+    /// Iterated X25519 test vectors from RFC 7748, section 5.2
+    #[test]
+    fn x25519_rfc7748_iterated() {
+        let mut k = constants::X25519_BASEPOINT.to_bytes();
+        let mut u = k;
+        for i in 0..1000 {
+            let result = x25519(k, u);
+            u = k;
+            k = result;
+
+            if i == 0 {
+                assert_eq!(k, [
+                    0x42, 0x2c, 0x8e, 0x7a, 0x62, 0x27, 0xd7, 0xbc,
+                    0xa1, 0x35, 0x0b, 0x3e, 0x2b, 0xb7, 0x27, 0x9f,
+                    0x78, 0x97, 0xb8, 0x7b, 0xb6, 0x85, 0x4b, 0x78,
+                    0x3c, 0x60, 0xe8, 0x03, 0x11, 0xae, 0x30, 0x79]);
+            }
+        }
+        assert_eq!(k, [
+            0x68, 0x4c, 0xf5, 0x9b, 0xa8, 0x33, 0x09, 0x55,
+            0x28, 0x00, 0xef, 0x56, 0x6f, 0x2f, 0x4d, 0x3c,
+            0x1c, 0x38, 0x87, 0xc4, 0x93, 0x60, 0xe3, 0x87,
+            0x5f, 0x2e, 0xb9, 0x4d, 0x99, 0x53, 0x2c, 0x51]);
+    }
+
+    #[test]
+    fn x25519_contributory_rejects_small_order() {
+        let secret = [0x42u8; 32];
+
+        // u = 0 and u = 1 generate subgroups of order 2 and 4
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        assert!(x25519_contributory(secret, [0u8; 32]).is_none());
+        assert!(x25519_contributory(secret, one).is_none());
+        for T in constants::EIGHT_TORSION.iter() {
+            assert!(x25519_contributory(secret, T.to_montgomery().to_bytes()).is_none());
+        }
+
+        let public = constants::X25519_BASEPOINT.to_bytes();
+        assert_eq!(x25519_contributory(secret, public), Some(x25519(secret, public)));
+    }
+
+    #[test]
+    fn small_order_and_on_curve_checks() {
+        // The RFC 7748 blocklist: 0, 1, the two order-8 u-coordinates,
+        // p - 1, p, and p + 1
+        let mut blocklist = [[0u8; 32]; 7];
+        blocklist[1][0] = 1;
+        blocklist[2] = [
+            0xe0, 0xeb, 0x7a, 0x7c, 0x3b, 0x41, 0xb8, 0xae, 0x16, 0x56, 0xe3, 0xfa, 0xf1, 0x9f, 0xc4, 0x6a,
+            0xda, 0x09, 0x8d, 0xeb, 0x9c, 0x32, 0xb1, 0xfd, 0x86, 0x62, 0x05, 0x16, 0x5f, 0x49, 0xb8, 0x00,
+        ];
+        blocklist[3] = [
+            0x5f, 0x9c, 0x95, 0xbc, 0xa3, 0x50, 0x8c, 0x24, 0xb1, 0xd0, 0xb1, 0x55, 0x9c, 0x83, 0xef, 0x5b,
+            0x04, 0x44, 0x5c, 0xc4, 0x58, 0x1c, 0x8e, 0x86, 0xd8, 0x22, 0x4e, 0xdd, 0xd0, 0x9f, 0x11, 0x57,
+        ];
+        for (i, low_byte) in [0xec, 0xed, 0xee].iter().enumerate() {
+            blocklist[4 + i] = [0xff; 32];
+            blocklist[4 + i][0] = *low_byte;
+            blocklist[4 + i][31] = 0x7f;
+        }
+
+        for bytes in blocklist.iter() {
+            let u = MontgomeryPoint(*bytes);
+            assert_eq!(u.is_small_order().unwrap_u8(), 1);
+            // The high bit is ignored
+            let mut high_bit = *bytes;
+            high_bit[31] |= 0x80;
+            assert_eq!(MontgomeryPoint(high_bit).is_small_order().unwrap_u8(), 1);
+        }
+
+        // The torsion points map to the blocklisted values on the curve
+        for T in constants::EIGHT_TORSION.iter() {
+            let u = T.to_montgomery();
+            assert!(blocklist.contains(&u.to_bytes()));
+            assert_eq!(u.is_on_curve().unwrap_u8(), 1);
+        }
+        // u = -1 is on the twist
+        assert_eq!(MontgomeryPoint(blocklist[4]).is_on_curve().unwrap_u8(), 0);
+
+        // Random u-coordinates: on the curve exactly when they map to
+        // an Edwards point, and almost never of small order
+        let mut rng = rand::thread_rng();
+        let mut on_curve = 0;
+        for _ in 0..64 {
+            let u = MontgomeryPoint(FieldElement::from_bytes(&rand::Rng::gen::<[u8; 32]>(&mut rng)).to_bytes());
+            assert_eq!(u.is_small_order().unwrap_u8(), 0);
+            assert_eq!(u.is_on_curve().unwrap_u8() == 1, u.to_edwards(0).is_some());
+            on_curve += u.is_on_curve().unwrap_u8();
+        }
+        assert!(on_curve > 0 && on_curve < 64);
+
+        let B = constants::X25519_BASEPOINT;
+        assert_eq!(B.is_on_curve().unwrap_u8(), 1);
+        assert_eq!(B.is_small_order().unwrap_u8(), 0);
+    }
+
+    #[test]
+    fn ct_eq_matches_eq() {
+        let u9 = constants::X25519_BASEPOINT;
+        let u18_unred = MontgomeryPoint([255; 32]);
+        assert_eq!(u9.ct_eq(&u9).unwrap_u8(), 1);
+        assert_eq!(u9.ct_eq(&u18_unred).unwrap_u8(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_bincode_basepoint_roundtrip() {
+        use bincode;
+
+        let encoded = bincode::serialize(&constants::X25519_BASEPOINT).unwrap();
+        let decoded: MontgomeryPoint = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, constants::X25519_BASEPOINT);
+
+        // A 31-byte encoding is rejected
+        let truncated = bincode::serialize(&[0u8; 31][..]).unwrap();
+        assert!(bincode::deserialize::<MontgomeryPoint>(&truncated).is_err());
+    }
+
 }
@@ -2,7 +2,11 @@
 
 // TODO: switch to alloc::prelude
 #[cfg(all(feature = "alloc", not(feature = "std")))]
+pub use alloc::boxed::Box;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 pub use alloc::vec::Vec;
 
+#[cfg(feature = "std")]
+pub use std::boxed::Box;
 #[cfg(feature = "std")]
 pub use std::vec::Vec;
@@ -710,6 +710,7 @@ impl RistrettoPoint {
     /// This function splits the input array into two 32-byte halves,
     /// takes the low 255 bits of each half mod p, applies the
     /// Ristretto-flavored Elligator map to each, and adds the results.
+    /// This is the hash-to-group map of RFC 9496, section 4.3.4.
     pub fn from_uniform_bytes(bytes: &[u8; 64]) -> RistrettoPoint {
         let mut r_1_bytes = [0u8; 32];
         r_1_bytes.copy_from_slice(&bytes[0..32]);
@@ -725,6 +726,44 @@ impl RistrettoPoint {
         // uniform distribution.
         &R_1 + &R_2
     }
+
+    /// Wrap an `EdwardsPoint` as a `RistrettoPoint`, checking that it
+    /// is a valid representative of a Ristretto group element.
+    ///
+    /// Every `RistrettoPoint` is represented internally by an
+    /// `EdwardsPoint` in \\(\mathcal E[4\ell]\\), the sum of the
+    /// prime-order subgroup and the \\(4\\)-torsion, which is the
+    /// image of the Ristretto decoding.  Points with a component of
+    /// order \\(8\\) do not represent any element of the group.
+    ///
+    /// This check costs a variable-base scalar multiplication, and
+    /// runs in variable time.
+    ///
+    /// # Return
+    ///
+    /// * `Some(RistrettoPoint)` if \\(4\ell P = 0\\);
+    ///
+    /// * `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::ristretto::RistrettoPoint;
+    ///
+    /// let B = constants::ED25519_BASEPOINT_POINT;
+    /// assert_eq!(RistrettoPoint::from_edwards_checked(B), Some(constants::RISTRETTO_BASEPOINT_POINT));
+    ///
+    /// let P = B + constants::EIGHT_TORSION[1];
+    /// assert!(RistrettoPoint::from_edwards_checked(P).is_none());
+    /// ```
+    pub fn from_edwards_checked(p: EdwardsPoint) -> Option<RistrettoPoint> {
+        if p.mul_by_pow_2(2).is_torsion_free() {
+            Some(RistrettoPoint(p))
+        } else {
+            None
+        }
+    }
 }
 
 impl Identity for RistrettoPoint {
@@ -955,7 +994,6 @@ impl VartimePrecomputedMultiscalarMul for VartimeRistrettoPrecomputation {
 impl RistrettoPoint {
     /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the
     /// Ristretto basepoint.
-    #[cfg(feature = "stage2_build")]
     pub fn vartime_double_scalar_mul_basepoint(
         a: &Scalar,
         A: &RistrettoPoint,
@@ -1010,6 +1048,29 @@ impl RistrettoBasepointTable {
     }
 }
 
+// Serializes as the compressed basepoint, like a `RistrettoPoint`,
+// rather than as the full table: the table is rebuilt with `create`
+// when it is deserialized.
+
+#[cfg(feature = "serde")]
+impl Serialize for RistrettoBasepointTable {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        self.basepoint().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for RistrettoBasepointTable {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let basepoint = RistrettoPoint::deserialize(deserializer)?;
+        Ok(RistrettoBasepointTable::create(&basepoint))
+    }
+}
+
 // ------------------------------------------------------------------------
 // Constant-time conditional selection
 // ------------------------------------------------------------------------
@@ -1100,6 +1161,27 @@ mod test {
         assert_eq!(dec_compressed, constants::RISTRETTO_BASEPOINT_COMPRESSED);
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_basepoint_table_roundtrip() {
+        use bincode;
+
+        let mut rng = rand::thread_rng();
+        let P = RistrettoPoint::random(&mut rng);
+        let table = RistrettoBasepointTable::create(&P);
+
+        // The table serializes as its basepoint
+        let encoded = bincode::serialize(&table).unwrap();
+        assert_eq!(encoded, bincode::serialize(&P).unwrap());
+        let decoded: RistrettoBasepointTable = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded.basepoint(), P);
+
+        let encoded = bincode::serialize(&constants::RISTRETTO_BASEPOINT_TABLE).unwrap();
+        let decoded: RistrettoBasepointTable = bincode::deserialize(&encoded).unwrap();
+        let s = Scalar::random(&mut rng);
+        assert_eq!(&decoded * &s, &constants::RISTRETTO_BASEPOINT_TABLE * &s);
+    }
+
     #[test]
     fn scalarmult_ristrettopoint_works_both_ways() {
         let P = constants::RISTRETTO_BASEPOINT_POINT;
@@ -1216,6 +1298,23 @@ mod test {
         }
     }
 
+    #[test]
+    fn from_edwards_checked() {
+        let B = constants::RISTRETTO_BASEPOINT_POINT;
+        for P in B.coset4().iter() {
+            assert_eq!(RistrettoPoint::from_edwards_checked(*P), Some(B));
+        }
+        for T in constants::EIGHT_TORSION.iter() {
+            let P = B.0 + T;
+            let expected = if T.mul_by_pow_2(2) == EdwardsPoint::identity() { Some(B) } else { None };
+            assert_eq!(RistrettoPoint::from_edwards_checked(P), expected);
+        }
+
+        // A point escaped from Ristretto arithmetic round-trips.
+        let P = B * Scalar::from(9876u64);
+        assert_eq!(RistrettoPoint::from_edwards_checked(P.0), Some(P));
+    }
+
     #[cfg(feature = "rand")]
     #[test]
     fn four_torsion_random() {
@@ -1228,6 +1327,55 @@ mod test {
         }
     }
 
+    #[test]
+    fn basepoint_table_for_custom_generator() {
+        let mut rng = rand::thread_rng();
+        let P = RistrettoPoint::random(&mut rng);
+        let table = RistrettoBasepointTable::create(&P);
+        assert_eq!(table.basepoint(), P);
+
+        for _ in 0..10 {
+            let s = Scalar::random(&mut rng);
+            assert_eq!(&table * &s, P * s);
+            assert_eq!(&s * &table, P * s);
+        }
+    }
+
+    #[test]
+    fn hash_from_bytes_rfc9496_vectors() {
+        // Test vectors from RFC 9496, Appendix A.3: each label is hashed
+        // with SHA-512 and the 64-byte output is mapped to the group.
+        let labels = [
+            "Ristretto is traditionally a short shot of espresso coffee",
+            "made with the normal amount of ground coffee but extracted with",
+            "about half the amount of water in the same amount of time",
+            "by using a finer grind.",
+            "This produces a concentrated shot of coffee per volume.",
+            "Just pulling a normal shot short will produce a weaker shot",
+            "and is not a Ristretto as some believe.",
+        ];
+        let encoded_images: [CompressedRistretto; 7] = [
+            CompressedRistretto([0x30, 0x66, 0xf8, 0x2a, 0x1a, 0x74, 0x7d, 0x45, 0x12, 0x0d, 0x17, 0x40, 0xf1, 0x43, 0x58, 0x53,
+                                 0x1a, 0x8f, 0x04, 0xbb, 0xff, 0xe6, 0xa8, 0x19, 0xf8, 0x6d, 0xfe, 0x50, 0xf4, 0x4a, 0x0a, 0x46]),
+            CompressedRistretto([0xf2, 0x6e, 0x5b, 0x6f, 0x7d, 0x36, 0x2d, 0x2d, 0x2a, 0x94, 0xc5, 0xd0, 0xe7, 0x60, 0x2c, 0xb4,
+                                 0x77, 0x3c, 0x95, 0xa2, 0xe5, 0xc3, 0x1a, 0x64, 0xf1, 0x33, 0x18, 0x9f, 0xa7, 0x6e, 0xd6, 0x1b]),
+            CompressedRistretto([0x00, 0x6c, 0xcd, 0x2a, 0x9e, 0x68, 0x67, 0xe6, 0xa2, 0xc5, 0xce, 0xa8, 0x3d, 0x33, 0x02, 0xcc,
+                                 0x9d, 0xe1, 0x28, 0xdd, 0x2a, 0x9a, 0x57, 0xdd, 0x8e, 0xe7, 0xb9, 0xd7, 0xff, 0xe0, 0x28, 0x26]),
+            CompressedRistretto([0xf8, 0xf0, 0xc8, 0x7c, 0xf2, 0x37, 0x95, 0x3c, 0x58, 0x90, 0xae, 0xc3, 0x99, 0x81, 0x69, 0x00,
+                                 0x5d, 0xae, 0x3e, 0xca, 0x1f, 0xbb, 0x04, 0x54, 0x8c, 0x63, 0x59, 0x53, 0xc8, 0x17, 0xf9, 0x2a]),
+            CompressedRistretto([0xae, 0x81, 0xe7, 0xde, 0xdf, 0x20, 0xa4, 0x97, 0xe1, 0x0c, 0x30, 0x4a, 0x76, 0x5c, 0x17, 0x67,
+                                 0xa4, 0x2d, 0x6e, 0x06, 0x02, 0x97, 0x58, 0xd2, 0xd7, 0xe8, 0xef, 0x7c, 0xc4, 0xc4, 0x11, 0x79]),
+            CompressedRistretto([0xe2, 0x70, 0x56, 0x52, 0xff, 0x9f, 0x5e, 0x44, 0xd3, 0xe8, 0x41, 0xbf, 0x1c, 0x25, 0x1c, 0xf7,
+                                 0xdd, 0xdb, 0x77, 0xd1, 0x40, 0x87, 0x0d, 0x1a, 0xb2, 0xed, 0x64, 0xf1, 0xa9, 0xce, 0x86, 0x28]),
+            CompressedRistretto([0x80, 0xbd, 0x07, 0x26, 0x25, 0x11, 0xcd, 0xde, 0x48, 0x63, 0xf8, 0xa7, 0x43, 0x4c, 0xef, 0x69,
+                                 0x67, 0x50, 0x68, 0x1c, 0xb9, 0x51, 0x0e, 0xea, 0x55, 0x70, 0x88, 0xf7, 0x6d, 0x9e, 0x50, 0x65]),
+        ];
+        for (label, image) in labels.iter().zip(encoded_images.iter()) {
+            let P = RistrettoPoint::hash_from_bytes::<sha2::Sha512>(label.as_bytes());
+            assert_eq!(P.compress(), *image);
+        }
+    }
+
     #[test]
     fn elligator_vs_ristretto_sage() {
         // Test vectors extracted from ristretto.sage.
@@ -140,6 +140,7 @@
 
 use core::borrow::Borrow;
 use core::cmp::{Eq, PartialEq};
+use core::convert::TryFrom;
 use core::fmt::Debug;
 use core::iter::{Product, Sum};
 use core::ops::Index;
@@ -159,6 +160,13 @@ use digest::Digest;
 use subtle::Choice;
 use subtle::ConditionallySelectable;
 use subtle::ConstantTimeEq;
+use subtle::CtOption;
+
+#[cfg(feature = "group")]
+use group::ff;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 use backend;
 use constants;
@@ -200,6 +208,11 @@ pub struct Scalar {
 impl Scalar {
     /// Construct a `Scalar` by reducing a 256-bit little-endian integer
     /// modulo the group order \\( \ell \\).
+    ///
+    /// To derive a uniformly distributed `Scalar` from hash output,
+    /// reduce 64 bytes with `from_bytes_mod_order_wide` (or use
+    /// `from_hash`) instead.  To keep the bits of `bytes` unreduced,
+    /// use `from_bits`.
     pub fn from_bytes_mod_order(bytes: [u8; 32]) -> Scalar {
         // Temporarily allow s_unreduced.bytes > 2^255 ...
         let s_unreduced = Scalar{bytes: bytes};
@@ -213,6 +226,8 @@ impl Scalar {
 
     /// Construct a `Scalar` by reducing a 512-bit little-endian integer
     /// modulo the group order \\( \ell \\).
+    ///
+    /// This is the reduction used by `from_hash` and `hash_from_bytes`.
     pub fn from_bytes_mod_order_wide(input: &[u8; 64]) -> Scalar {
         UnpackedScalar::from_bytes_wide(input).pack()
     }
@@ -225,15 +240,26 @@ impl Scalar {
     ///   if `bytes` is a canonical byte representation;
     /// - `None` if `bytes` is not a canonical byte representation.
     pub fn from_canonical_bytes(bytes: [u8; 32]) -> Option<Scalar> {
-        // Check that the high bit is not set
-        if (bytes[31] >> 7) != 0u8 { return None; }
+        Scalar::ct_from_canonical_bytes(bytes).into()
+    }
+
+    /// Attempt to construct a `Scalar` from a canonical byte
+    /// representation, in constant time.
+    ///
+    /// This is the constant-time version of `from_canonical_bytes`,
+    /// for callers (such as strict signature verification) which
+    /// must not leak whether the input was canonical.
+    ///
+    /// # Return
+    ///
+    /// A `CtOption` which is `Some(s)` if `bytes` is a canonical byte
+    /// representation of `s`, and `None` otherwise.
+    pub fn ct_from_canonical_bytes(bytes: [u8; 32]) -> CtOption<Scalar> {
+        // Check that the high bit is not set, and that the value is reduced.
+        let high_bit_unset = !Choice::from(bytes[31] >> 7);
         let candidate = Scalar::from_bits(bytes);
 
-        if candidate.is_canonical() {
-            Some(candidate)
-        } else {
-            None
-        }
+        CtOption::new(candidate, high_bit_unset & candidate.ct_is_canonical())
     }
 
     /// Construct a `Scalar` from the low 255 bits of a 256-bit integer.
@@ -248,6 +274,52 @@ impl Scalar {
 
         s
     }
+
+    /// Construct a `Scalar` from a 256-bit integer, clamped as an
+    /// Ed25519 or X25519 secret key.
+    ///
+    /// The low three bits and the high bit are cleared, and bit 254
+    /// is set, as described in RFC 7748 and RFC 8032.  Like
+    /// `from_bits`, the result is not reduced mod \\(\ell\\), so it
+    /// should only be used for scalar multiplication.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let s = Scalar::from_bits_clamped([0xff; 32]);
+    /// assert!(s.is_clamped());
+    /// assert!(!Scalar::from_bits([0xff; 32]).is_clamped());
+    /// ```
+    pub fn from_bits_clamped(mut bytes: [u8; 32]) -> Scalar {
+        bytes[0] &= 0b1111_1000;
+        bytes[31] &= 0b0111_1111;
+        bytes[31] |= 0b0100_0000;
+
+        Scalar{ bytes }
+    }
+
+    /// Construct a `Scalar` by reducing a 256-bit big-endian integer
+    /// modulo the group order \\( \ell \\).
+    ///
+    /// Like `from_bytes_mod_order`, this accepts any input.  Use
+    /// `to_bytes_be` to obtain the canonical big-endian encoding.
+    pub fn from_bytes_be(bytes: [u8; 32]) -> Scalar {
+        let mut le_bytes = bytes;
+        le_bytes.reverse();
+        Scalar::from_bytes_mod_order(le_bytes)
+    }
+
+    /// Construct a `Scalar` by reducing a 256-bit integer, given as
+    /// four little-endian `u64` limbs, modulo the group order
+    /// \\( \ell \\).
+    pub fn from_u64_limbs(limbs: [u64; 4]) -> Scalar {
+        use byteorder::{ByteOrder, LittleEndian};
+        let mut bytes = [0u8; 32];
+        LittleEndian::write_u64_into(&limbs, &mut bytes);
+        Scalar::from_bytes_mod_order(bytes)
+    }
 }
 
 impl Debug for Scalar {
@@ -256,6 +328,58 @@ impl Debug for Scalar {
     }
 }
 
+/// The error returned when a `Scalar` cannot be parsed from a slice
+/// of bytes with `TryFrom<&[u8]>`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ScalarFromSliceError {
+    /// The slice did not have a length of 32 bytes.
+    InvalidLength,
+    /// The bytes were not the canonical encoding of a scalar, that
+    /// is, they encoded an integer greater than or equal to
+    /// \\( \ell \\).
+    NonCanonical,
+}
+
+impl ::core::fmt::Display for ScalarFromSliceError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match *self {
+            ScalarFromSliceError::InvalidLength => write!(f, "scalar encoding must be 32 bytes"),
+            ScalarFromSliceError::NonCanonical => write!(f, "scalar encoding is not canonical"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ScalarFromSliceError {}
+
+impl<'a> TryFrom<&'a [u8]> for Scalar {
+    type Error = ScalarFromSliceError;
+
+    /// Parse a `Scalar` from its canonical 32-byte encoding.
+    ///
+    /// This rejects non-canonical encodings, as required for the
+    /// \\(s\\) component of strictly-verified Ed25519 signatures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::scalar::{Scalar, ScalarFromSliceError};
+    ///
+    /// let s = Scalar::from(42u64);
+    /// assert_eq!(Scalar::try_from(&s.to_bytes()[..]), Ok(s));
+    ///
+    /// let l = constants::BASEPOINT_ORDER.to_bytes();
+    /// assert_eq!(Scalar::try_from(&l[..]), Err(ScalarFromSliceError::NonCanonical));
+    /// assert_eq!(Scalar::try_from(&l[..31]), Err(ScalarFromSliceError::InvalidLength));
+    /// ```
+    fn try_from(bytes: &'a [u8]) -> Result<Scalar, ScalarFromSliceError> {
+        let bytes = <[u8; 32]>::try_from(bytes).map_err(|_| ScalarFromSliceError::InvalidLength)?;
+        Scalar::from_canonical_bytes(bytes).ok_or(ScalarFromSliceError::NonCanonical)
+    }
+}
+
 impl Eq for Scalar {}
 impl PartialEq for Scalar {
     fn eq(&self, other: &Self) -> bool {
@@ -329,6 +453,36 @@ impl<'a, 'b> Sub<&'b Scalar> for &'a Scalar {
 
 define_sub_variants!(LHS = Scalar, RHS = Scalar, Output = Scalar);
 
+// Arithmetic with small integers, which are converted with `From<u64>`
+// and then reduced as usual.
+
+impl<'b> Add<&'b u64> for &Scalar {
+    type Output = Scalar;
+    fn add(self, _rhs: &'b u64) -> Scalar {
+        self + Scalar::from(*_rhs)
+    }
+}
+
+define_add_variants!(LHS = Scalar, RHS = u64, Output = Scalar);
+
+impl<'b> Sub<&'b u64> for &Scalar {
+    type Output = Scalar;
+    fn sub(self, _rhs: &'b u64) -> Scalar {
+        self - Scalar::from(*_rhs)
+    }
+}
+
+define_sub_variants!(LHS = Scalar, RHS = u64, Output = Scalar);
+
+impl<'b> Mul<&'b u64> for &Scalar {
+    type Output = Scalar;
+    fn mul(self, _rhs: &'b u64) -> Scalar {
+        self * Scalar::from(*_rhs)
+    }
+}
+
+define_mul_variants!(LHS = Scalar, RHS = u64, Output = Scalar);
+
 impl<'a> Neg for &'a Scalar {
     type Output = Scalar;
     fn neg(self) -> Scalar {
@@ -357,13 +511,15 @@ impl ConditionallySelectable for Scalar {
 use serde::{self, Serialize, Deserialize, Serializer, Deserializer};
 #[cfg(feature = "serde")]
 use serde::de::Visitor;
+#[cfg(feature = "serde")]
+use serde_hex;
 
 #[cfg(feature = "serde")]
 impl Serialize for Scalar {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer
     {
-        serializer.serialize_bytes(self.reduce().as_bytes())
+        serde_hex::serialize(self.reduce().as_bytes(), serializer)
     }
 }
 
@@ -381,6 +537,13 @@ impl<'de> Deserialize<'de> for Scalar {
                 formatter.write_str("a canonically-encoded 32-byte scalar value")
             }
 
+            fn visit_str<E>(self, v: &str) -> Result<Scalar, E>
+                where E: serde::de::Error
+            {
+                let bytes = serde_hex::decode(v, &self)?;
+                self.visit_bytes(&bytes)
+            }
+
             fn visit_bytes<E>(self, v: &[u8]) -> Result<Scalar, E>
                 where E: serde::de::Error
             {
@@ -403,8 +566,115 @@ impl<'de> Deserialize<'de> for Scalar {
             }
         }
 
-        deserializer.deserialize_bytes(ScalarVisitor)
+        serde_hex::deserialize(deserializer, ScalarVisitor)
+    }
+}
+
+// Implements the `ff` crate's traits, so that `Scalar` can be used with
+// code that is generic over `ff::Field` and `ff::PrimeField`.
+
+#[cfg(feature = "group")]
+impl ff::Field for Scalar {
+    const ZERO: Self = Scalar { bytes: [0u8; 32] };
+    const ONE: Self = Scalar {
+        bytes: [
+            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ],
+    };
+
+    fn random(mut rng: impl rand_core_06::RngCore) -> Self {
+        let mut scalar_bytes = [0u8; 64];
+        rng.fill_bytes(&mut scalar_bytes);
+        Scalar::from_bytes_mod_order_wide(&scalar_bytes)
+    }
+
+    fn square(&self) -> Self {
+        Scalar::square(self)
+    }
+
+    fn double(&self) -> Self {
+        self + self
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        CtOption::new(self.invert(), !self.ct_eq(&Scalar::zero()))
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        // (t - 1) / 2, where t = (l - 1) / 2^S.
+        ff::helpers::sqrt_tonelli_shanks(
+            self,
+            [
+                0xcb02_4c63_4b9e_ba7d,
+                0x029b_df3b_d45e_f39a,
+                0x0000_0000_0000_0000,
+                0x0200_0000_0000_0000,
+            ],
+        )
+    }
+}
+
+#[cfg(feature = "group")]
+impl ff::PrimeField for Scalar {
+    type Repr = [u8; 32];
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        Scalar::ct_from_canonical_bytes(repr)
+    }
+
+    fn from_repr_vartime(repr: Self::Repr) -> Option<Self> {
+        Scalar::from_canonical_bytes(repr)
     }
+
+    fn to_repr(&self) -> Self::Repr {
+        self.reduce().to_bytes()
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from(self.reduce().bytes[0] & 1)
+    }
+
+    const MODULUS: &'static str =
+        "0x1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
+    const NUM_BITS: u32 = 253;
+    const CAPACITY: u32 = 252;
+
+    const TWO_INV: Self = Scalar {
+        bytes: [
+            0xf7, 0xe9, 0x7a, 0x2e, 0x8d, 0x31, 0x09, 0x2c, 0x6b, 0xce, 0x7b, 0x51, 0xef, 0x7c, 0x6f, 0x0a,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08,
+        ],
+    };
+    const MULTIPLICATIVE_GENERATOR: Self = Scalar {
+        bytes: [
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ],
+    };
+    const S: u32 = 2;
+    const ROOT_OF_UNITY: Self = Scalar {
+        bytes: [
+            0xd4, 0x07, 0xbe, 0xeb, 0xdf, 0x75, 0x87, 0xbe, 0xfe, 0x83, 0xce, 0x42, 0x53, 0x56, 0xf0, 0x0e,
+            0x7a, 0xc2, 0xc1, 0xab, 0x60, 0x6d, 0x3d, 0x7d, 0xe7, 0x81, 0x79, 0xe0, 0x10, 0x73, 0x4a, 0x09,
+        ],
+    };
+    const ROOT_OF_UNITY_INV: Self = Scalar {
+        bytes: [
+            0x19, 0xcc, 0x37, 0x71, 0x3a, 0xed, 0x8a, 0x99, 0xd7, 0x18, 0x29, 0x60, 0x8b, 0xa3, 0xee, 0x05,
+            0x86, 0x3d, 0x3e, 0x54, 0x9f, 0x92, 0xc2, 0x82, 0x18, 0x7e, 0x86, 0x1f, 0xef, 0x8c, 0xb5, 0x06,
+        ],
+    };
+    const DELTA: Self = Scalar {
+        bytes: [
+            16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ],
+    };
 }
 
 impl<T> Product<T> for Scalar
@@ -431,6 +701,129 @@ where
     }
 }
 
+/// A sum of `Scalar`s whose reduction modulo \\( \ell \\) is deferred
+/// until `reduce` is called.
+///
+/// Adding a `Scalar` into the sum is a plain 256-bit integer addition,
+/// and subtracting one adds \\( 16\ell - s \\), which is never
+/// negative; neither reduces.  The value of the accumulator is
+/// therefore congruent to the sum mod \\( \ell \\), but may be as
+/// large as about \\( 2\^{320} \\), and is only meaningful once
+/// `reduce` has produced a `Scalar`.  The result is the same as
+/// summing with `Scalar` arithmetic, including for unreduced
+/// `Scalar`s.
+///
+/// The accumulator has room for \\( 2\^{62} \\) operations.  Like the
+/// other `Scalar` arithmetic, additions and subtractions run in
+/// constant time.
+///
+/// ```
+/// # use curve25519_dalek::scalar::{Scalar, UnreducedScalarSum};
+/// let scalars: Vec<Scalar> = (1..100u64).map(|i| Scalar::from(i).invert()).collect();
+///
+/// let mut sum = UnreducedScalarSum::default();
+/// for s in scalars.iter() {
+///     sum += s;
+/// }
+/// sum -= &scalars[0];
+///
+/// let expected: Scalar = scalars[1..].iter().sum();
+/// assert_eq!(sum.reduce(), expected);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UnreducedScalarSum {
+    /// The sum, as a little-endian integer in 64-bit limbs.
+    limbs: [u64; 5],
+}
+
+/// \\( 16\ell \\) in 64-bit limbs, which exceeds every 256-bit integer.
+const SIXTEEN_L: [u64; 5] = [0x812631a5cf5d3ed0, 0x4def9dea2f79cd65, 1, 0, 1];
+
+impl UnreducedScalarSum {
+    /// Add the 320-bit integer `rhs` into the sum.
+    fn add_limbs(&mut self, rhs: &[u64; 5]) {
+        let mut carry = 0u128;
+        for (limb, r) in self.limbs.iter_mut().zip(rhs.iter()) {
+            carry += (*limb as u128) + (*r as u128);
+            *limb = carry as u64;
+            carry >>= 64;
+        }
+    }
+
+    /// Reduce the sum modulo \\( \ell \\).
+    pub fn reduce(&self) -> Scalar {
+        let mut wide = [0u8; 64];
+        for (chunk, limb) in wide.chunks_mut(8).zip(self.limbs.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        Scalar::from_bytes_mod_order_wide(&wide)
+    }
+}
+
+impl<'a> AddAssign<&'a Scalar> for UnreducedScalarSum {
+    fn add_assign(&mut self, rhs: &'a Scalar) {
+        let s = rhs.to_u64_limbs();
+        self.add_limbs(&[s[0], s[1], s[2], s[3], 0]);
+    }
+}
+
+impl AddAssign<Scalar> for UnreducedScalarSum {
+    fn add_assign(&mut self, rhs: Scalar) {
+        *self += &rhs;
+    }
+}
+
+impl<'a> SubAssign<&'a Scalar> for UnreducedScalarSum {
+    fn sub_assign(&mut self, rhs: &'a Scalar) {
+        // Compute 16*l - s, which is positive since s < 2^256 < 16*l.
+        let s = rhs.to_u64_limbs();
+        let mut negated = [0u64; 5];
+        let mut borrow = 0u128;
+        for i in 0..5 {
+            let s_i = if i < 4 { s[i] as u128 } else { 0 };
+            let diff = (SIXTEEN_L[i] as u128).wrapping_sub(s_i + borrow);
+            negated[i] = diff as u64;
+            borrow = diff >> 127;
+        }
+        self.add_limbs(&negated);
+    }
+}
+
+impl SubAssign<Scalar> for UnreducedScalarSum {
+    fn sub_assign(&mut self, rhs: Scalar) {
+        *self -= &rhs;
+    }
+}
+
+impl<T> Sum<T> for UnreducedScalarSum
+where
+    T: Borrow<Scalar>
+{
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = T>
+    {
+        iter.fold(UnreducedScalarSum::default(), |mut acc, item| {
+            acc += item.borrow();
+            acc
+        })
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for UnreducedScalarSum {
+    fn zeroize(&mut self) {
+        self.limbs.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Scalar {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
 impl Default for Scalar {
     fn default() -> Scalar {
         Scalar::zero()
@@ -505,6 +898,12 @@ impl From<u128> for Scalar {
 impl Scalar {
     /// Return a `Scalar` chosen uniformly at random using a user-provided RNG.
     ///
+    /// This draws 64 bytes from the RNG and reduces them with
+    /// `from_bytes_mod_order_wide`, so the bias from the reduction is
+    /// negligible.  It depends only on the `rand_core` traits, not on
+    /// `rand`, so it can be used in `no_std` code with any RNG which
+    /// implements them.
+    ///
     /// # Inputs
     ///
     /// * `rng`: any RNG which implements the `RngCore + CryptoRng` interface.
@@ -570,6 +969,9 @@ impl Scalar {
     /// to stream data into the `Digest` than to pass a single byte
     /// slice.
     ///
+    /// The 64-byte digest is reduced with `from_bytes_mod_order_wide`,
+    /// so the result is statistically close to uniform modulo \\( \ell \\).
+    ///
     /// # Example
     ///
     /// ```
@@ -636,6 +1038,24 @@ impl Scalar {
         &self.bytes
     }
 
+    /// Convert this `Scalar` to its underlying sequence of bytes, in
+    /// big-endian order.
+    ///
+    /// This is the reverse of `to_bytes`.
+    pub fn to_bytes_be(&self) -> [u8; 32] {
+        let mut bytes = self.bytes;
+        bytes.reverse();
+        bytes
+    }
+
+    /// Convert this `Scalar` to four little-endian `u64` limbs.
+    pub fn to_u64_limbs(&self) -> [u64; 4] {
+        use byteorder::{ByteOrder, LittleEndian};
+        let mut limbs = [0u64; 4];
+        LittleEndian::read_u64_into(&self.bytes, &mut limbs);
+        limbs
+    }
+
     /// Construct the scalar \\( 0 \\).
     pub fn zero() -> Self {
         Scalar { bytes: [0u8; 32]}
@@ -701,11 +1121,10 @@ impl Scalar {
     ///
     /// The product of all inverses is returned.
     ///
-    /// # Warning
-    ///
-    /// All input `Scalars` **MUST** be nonzero.  If you cannot
-    /// *prove* that this is the case, you **SHOULD NOT USE THIS
-    /// FUNCTION**.
+    /// Zero inputs are left as zero, and are skipped when computing
+    /// the inverses of the other inputs and the returned product.
+    /// This is done in constant time, so it does not reveal which
+    /// inputs were zero.
     ///
     /// # Example
     ///
@@ -727,6 +1146,15 @@ impl Scalar {
     /// assert_eq!(scalars[1], Scalar::from(5u64).invert());
     /// assert_eq!(scalars[2], Scalar::from(7u64).invert());
     /// assert_eq!(scalars[3], Scalar::from(11u64).invert());
+    ///
+    /// let mut scalars = [Scalar::from(3u64), Scalar::zero(), Scalar::from(5u64)];
+    ///
+    /// let allinv = Scalar::batch_invert(&mut scalars);
+    ///
+    /// assert_eq!(allinv, Scalar::from(3*5u64).invert());
+    /// assert_eq!(scalars[0], Scalar::from(3u64).invert());
+    /// assert_eq!(scalars[1], Scalar::zero());
+    /// assert_eq!(scalars[2], Scalar::from(5u64).invert());
     /// # }
     /// ```
     #[cfg(feature = "alloc")]
@@ -755,20 +1183,20 @@ impl Scalar {
         let mut acc = Scalar::one().unpack().to_montgomery();
 
         // Pass through the input vector, recording the previous
-        // products in the scratch space
+        // products in the scratch space.  Zero inputs are replaced
+        // by one so that they don't zero out the accumulator.
         for (input, scratch) in inputs.iter_mut().zip(scratch.iter_mut()) {
             *scratch = acc;
 
             // Avoid unnecessary Montgomery multiplication in second pass by
-            // keeping inputs in Montgomery form
+            // keeping inputs in Montgomery form.  Zero is still zero
+            // in Montgomery form, so zero inputs stay recognizable.
             let tmp = input.unpack().to_montgomery();
             *input = tmp.pack();
-            acc = UnpackedScalar::montgomery_mul(&acc, &tmp);
+            let input_or_one = Scalar::conditional_select(input, &one.pack(), input.ct_eq(&Scalar::zero()));
+            acc = UnpackedScalar::montgomery_mul(&acc, &input_or_one.unpack());
         }
 
-        // acc is nonzero iff all inputs are nonzero
-        debug_assert!(acc.pack() != Scalar::zero());
-
         // Compute the inverse of all products
         acc = acc.montgomery_invert().from_montgomery();
 
@@ -776,18 +1204,81 @@ impl Scalar {
         let ret = acc.pack();
 
         // Pass through the vector backwards to compute the inverses
-        // in place
+        // in place, skipping over the zero inputs
         for (input, scratch) in inputs.iter_mut().rev().zip(scratch.into_iter().rev()) {
-            let tmp = UnpackedScalar::montgomery_mul(&acc, &input.unpack());
-            *input = UnpackedScalar::montgomery_mul(&acc, &scratch).pack();
+            let input_is_zero = input.ct_eq(&Scalar::zero());
+            let input_or_one = Scalar::conditional_select(input, &one.pack(), input_is_zero);
+            let tmp = UnpackedScalar::montgomery_mul(&acc, &input_or_one.unpack());
+            let inverse = UnpackedScalar::montgomery_mul(&acc, &scratch).pack();
+            *input = Scalar::conditional_select(&inverse, &Scalar::zero(), input_is_zero);
             acc = tmp;
         }
 
         ret
     }
 
-    /// Get the bits of the scalar.
-    pub(crate) fn bits(&self) -> [i8; 256] {
+    /// Compute the square of this `Scalar`.
+    pub fn square(&self) -> Scalar {
+        self.unpack().square().pack()
+    }
+
+    /// Raise this `Scalar` to the power `exp`.
+    ///
+    /// This is constant-time with respect to both `self` and `exp`:
+    /// it uses a square-and-multiply ladder over all 256 bits of
+    /// `exp`, in Montgomery form, selecting the product in constant
+    /// time rather than branching on the bits of `exp`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # use curve25519_dalek::scalar::Scalar;
+    /// # fn main() {
+    /// let x = Scalar::from(3u64);
+    ///
+    /// assert_eq!(x.pow(&Scalar::from(4u64)), Scalar::from(81u64));
+    /// # }
+    /// ```
+    pub fn pow(&self, exp: &Scalar) -> Scalar {
+        let x = self.unpack().to_montgomery();
+        let mut acc = Scalar::one().unpack().to_montgomery();
+
+        for i in (0..256).rev() {
+            acc = acc.montgomery_square();
+            let acc_times_x = UnpackedScalar::montgomery_mul(&acc, &x);
+            let bit = Choice::from((exp.bytes[i >> 3] >> (i & 7)) & 1);
+            acc = UnpackedScalar::conditional_select(&acc, &acc_times_x, bit);
+        }
+
+        acc.from_montgomery().pack()
+    }
+
+    /// Raise this `Scalar` to the power `exp_bits`, in variable time.
+    ///
+    /// The exponent is given as little-endian 64-bit limbs, so for
+    /// instance `[5, 1]` is \\(2\^{64} + 5\\), and may be of any length.
+    ///
+    /// This is **not** constant-time with respect to the exponent,
+    /// so it should only be used with public exponents.
+    pub fn pow_vartime(&self, exp_bits: &[u64]) -> Scalar {
+        let x = self.unpack().to_montgomery();
+        let mut acc = Scalar::one().unpack().to_montgomery();
+
+        for limb in exp_bits.iter().rev() {
+            for i in (0..64).rev() {
+                acc = acc.montgomery_square();
+                if (limb >> i) & 1 == 1 {
+                    acc = UnpackedScalar::montgomery_mul(&acc, &x);
+                }
+            }
+        }
+
+        acc.from_montgomery().pack()
+    }
+
+    /// Get the bits of the scalar as an array, least significant first.
+    pub(crate) fn bit_array(&self) -> [i8; 256] {
         let mut bits = [0i8; 256];
         for i in 0..256 {
             // As i runs from 0..256, the bottom 3 bits index the bit,
@@ -797,6 +1288,46 @@ impl Scalar {
         bits
     }
 
+    /// Iterate over the 256 bits of this `Scalar`'s byte representation,
+    /// least significant bit first.
+    ///
+    /// The iterator is double-ended, so `s.bits().rev()` yields the
+    /// bits most significant first.
+    ///
+    /// # Warning
+    ///
+    /// Consumers of the iterator will usually branch on the bits, so
+    /// this should only be used with public scalars.  For secret
+    /// scalars, use [`Scalar::bit`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use curve25519_dalek::scalar::Scalar;
+    /// let s = Scalar::from(6u64);
+    /// let low_bits: Vec<bool> = s.bits().take(4).collect();
+    ///
+    /// assert_eq!(low_bits, vec![false, true, true, false]);
+    /// ```
+    pub fn bits(&self) -> impl DoubleEndedIterator<Item = bool> + ExactSizeIterator {
+        let bytes = self.bytes;
+        (0..256).map(move |i| (bytes[i >> 3] >> (i & 7)) & 1 == 1)
+    }
+
+    /// Get the \\(i\\)-th bit of this `Scalar`'s byte representation,
+    /// where bit \\(0\\) is the least significant.
+    ///
+    /// This runs in constant time with respect to the value of the
+    /// scalar (but not the index), so it can be used with secret
+    /// scalars.
+    ///
+    /// # Panics
+    ///
+    /// If `i >= 256`.
+    pub fn bit(&self, i: usize) -> Choice {
+        Choice::from((self.bytes[i >> 3] >> (i & 7)) & 1)
+    }
+
     /// Compute a width-\\(w\\) "Non-Adjacent Form" of this scalar.
     ///
     /// A width-\\(w\\) NAF of a positive integer \\(k\\) is an expression
@@ -835,7 +1366,7 @@ impl Scalar {
     ///
     /// Here \\( \bar x = x \operatorname{mods} 2^w \\) means the
     /// \\( \bar x \\) with \\( \bar x \equiv x \pmod{2^w} \\) and
-    /// \\( -2^{w-1} \leq \bar x < 2^w \\).
+    /// \\( -2^{w-1} \leq \bar x < 2^{w-1} \\).
     ///
     /// We implement this by scanning across the bits of \\(k\\) from
     /// least-significant bit to most-significant-bit.
@@ -869,11 +1400,26 @@ impl Scalar {
     /// If \\( k \mod 2^w\\) is even, we emit \\(0\\), advance 1 bit
     /// and reindex.  In fact, by setting all digits to \\(0\\)
     /// initially, we don't need to emit anything.
-    pub(crate) fn non_adjacent_form(&self, w: usize) -> [i8; 256] {
-        // required by the NAF definition
-        debug_assert!( w >= 2 );
-        // required so that the NAF digits fit in i8
-        debug_assert!( w <= 8 );
+    ///
+    /// # Return
+    ///
+    /// The \\(256\\) coefficients \\(n\_0, \ldots, n\_{255}\\),
+    /// least significant first, each of which is either zero or odd
+    /// with \\(|n\_i| < 2\^{w-1}\\).
+    ///
+    /// # Warning
+    ///
+    /// This function branches on the bits of the scalar, so it is
+    /// **not** constant-time, and should only be used with public
+    /// scalars.
+    ///
+    /// # Panics
+    ///
+    /// If `w` is not between 2 and 8.
+    pub fn non_adjacent_form(&self, w: usize) -> [i8; 256] {
+        // 2 is required by the NAF definition, and 8 is required so
+        // that the NAF digits fit in i8.
+        assert!((2..=8).contains(&w), "NAF width must be between 2 and 8");
 
         use byteorder::{ByteOrder, LittleEndian};
 
@@ -917,7 +1463,9 @@ impl Scalar {
                 naf[pos] = window as i8;
             } else {
                 carry = 1;
-                naf[pos] = (window as i8) - (width as i8);
+                // Computed with wrapping arithmetic, since `width as i8`
+                // wraps when w = 7 or w = 8.
+                naf[pos] = (window as i8).wrapping_sub(width as i8);
             }
 
             pos += w;
@@ -963,30 +1511,33 @@ impl Scalar {
 
     /// Returns a size hint indicating how many entries of the return
     /// value of `to_radix_2w` are nonzero.
-    pub(crate) fn to_radix_2w_size_hint(w: usize) -> usize {
-        debug_assert!(w >= 6);
-        debug_assert!(w <= 8);
-
+    ///
+    /// This is \\(\lceil 256/w \rceil\\), plus one when \\(w = 8\\)
+    /// to hold the final carry.
+    ///
+    /// # Panics
+    ///
+    /// If `w` is not between 4 and 8.
+    pub fn to_radix_2w_size_hint(w: usize) -> usize {
         let digits_count = match w {
-            6 => (256 + w - 1)/w as usize,
-            7 => (256 + w - 1)/w as usize,
+            4..=7 => (256 + w - 1)/w as usize,
             // See comment in to_radix_2w on handling the terminal carry.
             8 => (256 + w - 1)/w + 1 as usize,
             _ => panic!("invalid radix parameter"),
         };
 
-        debug_assert!(digits_count <= 43);
+        debug_assert!(digits_count <= 64);
         digits_count
     }
 
-    /// Creates a representation of a Scalar in radix 64, 128 or 256 for use with the Pippenger algorithm.
-    /// For lower radix, use `to_radix_16`, which is used by the Straus multi-scalar multiplication.
+    /// Creates a representation of a Scalar in radix 16, 32, 64, 128 or 256
+    /// for use with the Pippenger algorithm and the precomputed basepoint tables.
     /// Higher radixes are not supported to save cache space. Radix 256 is near-optimal even for very
     /// large inputs.
     ///
-    /// Radix below 64 or above 256 is prohibited.
+    /// Radix below 16 or above 256 is prohibited.
     /// This method returns digits in a fixed-sized array, excess digits are zeroes.
-    /// The second returned value is the number of digits.
+    /// Use `to_radix_2w_size_hint` to find the number of digits.
     ///
     /// ## Scalar representation
     ///
@@ -997,9 +1548,21 @@ impl Scalar {
     /// $$
     /// with \\(-2\^w/2 \leq a_i < 2\^w/2\\) for \\(0 \leq i < (n-1)\\) and \\(-2\^w/2 \leq a_{n-1} \leq 2\^w/2\\).
     ///
-    pub(crate) fn to_radix_2w(&self, w: usize) -> [i8; 43] {
-        debug_assert!(w >= 6);
-        debug_assert!(w <= 8);
+    /// When \\(w = 8\\) there are \\(n + 1\\) digits, where the last
+    /// one is the final carry.
+    ///
+    /// Unlike `non_adjacent_form`, this runs in constant time, so it
+    /// can be used for constant-time windowed multiplication.
+    ///
+    /// # Panics
+    ///
+    /// If `w` is not between 4 and 8.
+    pub fn to_radix_2w(&self, w: usize) -> [i8; 64] {
+        assert!((4..=8).contains(&w), "radix must be between 2^4 and 2^8");
+
+        if w == 4 {
+            return self.to_radix_16();
+        }
 
         use byteorder::{ByteOrder, LittleEndian};
 
@@ -1011,7 +1574,7 @@ impl Scalar {
         let window_mask: u64 = radix - 1;
 
         let mut carry = 0u64;
-        let mut digits = [0i8; 43];
+        let mut digits = [0i8; 64];
         let digits_count = (256 + w - 1)/w as usize;
         for i in 0..digits_count {
             // Construct a buffer of bits of the scalar, starting at `bit_offset`.
@@ -1070,7 +1633,8 @@ impl Scalar {
 
     /// Check whether this `Scalar` is the canonical representative mod \\(\ell\\).
     ///
-    /// This is intended for uses like input validation, where variable-time code is acceptable.
+    /// This is intended for uses like input validation, where variable-time code is acceptable;
+    /// see `ct_is_canonical` for a constant-time version.
     ///
     /// ```
     /// # extern crate curve25519_dalek;
@@ -1087,7 +1651,83 @@ impl Scalar {
     /// # }
     /// ```
     pub fn is_canonical(&self) -> bool {
-        *self == self.reduce()
+        self.ct_is_canonical().unwrap_u8() == 1u8
+    }
+
+    /// Check whether this `Scalar` has the bit pattern of a clamped
+    /// secret key, as produced by `from_bits_clamped`: the low three
+    /// bits and bit 255 are clear, and bit 254 is set.
+    ///
+    /// Since reduction mod \\(\ell\\) destroys this pattern, this is
+    /// only meaningful for scalars which have not been reduced.
+    pub fn is_clamped(&self) -> bool {
+        self.bytes[0] & 0b0000_0111 == 0 && self.bytes[31] & 0b1100_0000 == 0b0100_0000
+    }
+
+    /// Check in constant time whether this `Scalar` is the canonical
+    /// representative mod \\(\ell\\).
+    ///
+    /// Returns a `Choice` which is set exactly when the bytes of this
+    /// `Scalar` encode an integer less than \\(\ell\\).
+    pub fn ct_is_canonical(&self) -> Choice {
+        self.ct_eq(&self.reduce())
+    }
+
+    /// Check in constant time whether this `Scalar` is greater than
+    /// `other`.
+    ///
+    /// Both scalars are compared as integers in \\([0, \ell)\\), that
+    /// is, by their canonical reduced values, so an unreduced `Scalar`
+    /// compares the same as its reduction.  To check whether the bytes
+    /// of a `Scalar` encode an integer less than \\(\ell\\), use
+    /// `ct_is_canonical`.
+    ///
+    /// ```
+    /// # extern crate curve25519_dalek;
+    /// # use curve25519_dalek::scalar::Scalar;
+    /// # fn main() {
+    /// let two = Scalar::from(2u64);
+    /// let three = Scalar::from(3u64);
+    ///
+    /// assert!(bool::from(three.ct_gt(&two)));
+    /// assert!(bool::from(two.ct_lt(&three)));
+    /// assert!(bool::from(two.ct_ge(&two)));
+    ///
+    /// // -1 is the largest scalar, l - 1
+    /// assert!(bool::from((-Scalar::one()).ct_gt(&three)));
+    /// # }
+    /// ```
+    pub fn ct_gt(&self, other: &Scalar) -> Choice {
+        let a = self.reduce().bytes;
+        let b = other.reduce().bytes;
+
+        // Scan from the most significant byte, recording whether a > b
+        // at the first byte where they differ.
+        let mut gt = 0u16;
+        let mut eq = 1u16;
+        for i in (0..32).rev() {
+            let (a_i, b_i) = (a[i] as u16, b[i] as u16);
+            // b_i - a_i borrows exactly when a_i > b_i
+            let byte_gt = (b_i.wrapping_sub(a_i) >> 8) & 1;
+            // (a_i ^ b_i) - 1 borrows exactly when a_i == b_i
+            let byte_eq = ((a_i ^ b_i).wrapping_sub(1) >> 8) & 1;
+            gt |= eq & byte_gt;
+            eq &= byte_eq;
+        }
+        Choice::from(gt as u8)
+    }
+
+    /// Check in constant time whether this `Scalar` is less than
+    /// `other`, comparing canonical reduced values as in `ct_gt`.
+    pub fn ct_lt(&self, other: &Scalar) -> Choice {
+        other.ct_gt(self)
+    }
+
+    /// Check in constant time whether this `Scalar` is greater than or
+    /// equal to `other`, comparing canonical reduced values as in
+    /// `ct_gt`.
+    pub fn ct_ge(&self, other: &Scalar) -> Choice {
+        !self.ct_lt(other)
     }
 }
 
@@ -1271,6 +1911,38 @@ mod test {
         }
     }
 
+    #[test]
+    fn non_adjacent_form_all_widths() {
+        let two = Scalar::from(2u64);
+        for w in 2..=8 {
+            let naf = X.non_adjacent_form(w);
+
+            let mut recovered_scalar = Scalar::zero();
+            for digit in naf.iter().rev() {
+                assert!(*digit == 0 || (digit & 1 == 1 && (digit.abs() as i16) < (1 << (w - 1))));
+                let sdigit = if *digit < 0 {
+                    -Scalar::from((-(*digit as i64)) as u64)
+                } else {
+                    Scalar::from(*digit as u64)
+                };
+                recovered_scalar = recovered_scalar * two + sdigit;
+            }
+            assert_eq!(recovered_scalar, X);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_adjacent_form_rejects_width_9() {
+        X.non_adjacent_form(9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn to_radix_2w_rejects_radix_8() {
+        X.to_radix_2w(3);
+    }
+
     #[test]
     fn from_u64() {
         let val: u64 = 0xdeadbeefdeadbeef;
@@ -1285,6 +1957,103 @@ mod test {
         assert_eq!(s[0], 0xef);
     }
 
+    #[test]
+    fn try_from_slice() {
+        assert_eq!(Scalar::try_from(&X.to_bytes()[..]), Ok(X));
+        assert_eq!(Scalar::try_from(&[0u8; 0][..]), Err(ScalarFromSliceError::InvalidLength));
+        assert_eq!(Scalar::try_from(&[0u8; 33][..]), Err(ScalarFromSliceError::InvalidLength));
+
+        // l - 1 is the largest canonical scalar
+        let l_minus_one = -Scalar::one();
+        assert_eq!(Scalar::try_from(&l_minus_one.to_bytes()[..]), Ok(l_minus_one));
+
+        // Values with the high bit set are never canonical
+        let mut high_bit = X.to_bytes();
+        high_bit[31] |= 0x80;
+        assert_eq!(Scalar::try_from(&high_bit[..]), Err(ScalarFromSliceError::NonCanonical));
+    }
+
+    #[test]
+    fn big_endian_and_limb_roundtrips() {
+        let mut be_bytes = X.to_bytes();
+        be_bytes.reverse();
+        assert_eq!(X.to_bytes_be(), be_bytes);
+        assert_eq!(Scalar::from_bytes_be(X.to_bytes_be()), X);
+
+        let limbs = X.to_u64_limbs();
+        assert_eq!(limbs[0], 0x8408475d34b45a4e);
+        assert_eq!(limbs[3], 0x04f2d979a8f449d4);
+        assert_eq!(Scalar::from_u64_limbs(limbs), X);
+
+        // Both constructors reduce mod l
+        let l = constants::BASEPOINT_ORDER;
+        assert_eq!(Scalar::from_bytes_be(l.to_bytes_be()), Scalar::zero());
+        assert_eq!(Scalar::from_u64_limbs(l.to_u64_limbs()), Scalar::zero());
+        assert_eq!(Scalar::from_u64_limbs([0xffff_ffff_ffff_ffff; 4]),
+                   Scalar::from_bytes_mod_order([0xff; 32]));
+    }
+
+    #[test]
+    fn bit_iterator_and_accessor() {
+        let bits: Vec<bool> = X.bits().collect();
+        assert_eq!(bits.len(), 256);
+        let bit_array = X.bit_array();
+        for (i, b) in bits.iter().enumerate() {
+            assert_eq!(*b, X.bit(i).unwrap_u8() == 1);
+            assert_eq!(*b, bit_array[i] == 1);
+        }
+
+        // Reassembling the bits gives back the bytes
+        let mut bytes = [0u8; 32];
+        for (i, b) in X.bits().enumerate() {
+            bytes[i / 8] |= (b as u8) << (i % 8);
+        }
+        assert_eq!(bytes, X.to_bytes());
+
+        // Reversing yields the most significant bit first
+        assert_eq!(Scalar::one().bits().rev().position(|b| b), Some(255));
+    }
+
+    #[test]
+    fn unreduced_sum_matches_reduced_arithmetic() {
+        let mut rng = rand::thread_rng();
+        let l = constants::BASEPOINT_ORDER;
+        let minus_one = -Scalar::one();
+        // Include unreduced scalars, up to 2^255 - 1
+        let unreduced = [Scalar::from_bits([0xff; 32]), l, Scalar::from_bits(l.bytes)];
+
+        let mut sum = UnreducedScalarSum::default();
+        let mut expected = Scalar::zero();
+        for i in 0..1000 {
+            let s = match i % 5 {
+                0 => Scalar::random(&mut rng),
+                1 => minus_one,
+                2 => unreduced[i % 3],
+                3 => Scalar::zero(),
+                _ => Scalar::from(i as u64),
+            };
+            if i % 3 == 0 {
+                sum -= &s;
+                expected -= s.reduce();
+            } else {
+                sum += s;
+                expected += s.reduce();
+            }
+        }
+        assert_eq!(sum.reduce(), expected);
+        assert!(sum.reduce().is_canonical());
+
+        // Subtracting a value from itself gives zero
+        let mut zero = UnreducedScalarSum::default();
+        zero += &unreduced[0];
+        zero -= &unreduced[0];
+        assert_eq!(zero.reduce(), Scalar::zero());
+
+        let scalars = [X, Y, minus_one];
+        let summed: UnreducedScalarSum = scalars.iter().sum();
+        assert_eq!(summed.reduce(), scalars.iter().sum());
+    }
+
     #[test]
     fn scalar_mul_by_one() {
         let test_scalar = &X * &Scalar::one();
@@ -1301,6 +2070,57 @@ mod test {
         assert_eq!(should_be_two, two);
     }
 
+    #[test]
+    fn random_uses_64_bytes_from_rng() {
+        /// A deterministic RNG, implementing only the `rand_core` traits.
+        struct CountingRng(u8);
+
+        impl RngCore for CountingRng {
+            fn next_u32(&mut self) -> u32 {
+                rand_core::impls::next_u32_via_fill(self)
+            }
+            fn next_u64(&mut self) -> u64 {
+                rand_core::impls::next_u64_via_fill(self)
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                for byte in dest.iter_mut() {
+                    *byte = self.0;
+                    self.0 = self.0.wrapping_add(1);
+                }
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        impl CryptoRng for CountingRng {}
+
+        let mut rng = CountingRng(0);
+        let mut expected_bytes = [0u8; 64];
+        for (i, byte) in expected_bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+
+        assert_eq!(Scalar::random(&mut rng), Scalar::from_bytes_mod_order_wide(&expected_bytes));
+        // Exactly 64 bytes were drawn
+        assert_eq!(rng.0, 64);
+    }
+
+    #[test]
+    fn impl_ops_with_u64() {
+        let five = Scalar::from(5u64);
+        assert_eq!(X + 5u64, X + five);
+        assert_eq!(&X - 5u64, X - five);
+        assert_eq!(&X * 5u64, X * five);
+
+        // The results are reduced
+        let l_minus_one = Scalar::zero() - 1u64;
+        assert_eq!(l_minus_one, -Scalar::one());
+        assert_eq!(l_minus_one + 1u64, Scalar::zero());
+        assert_eq!(l_minus_one * u64::MAX, Scalar::zero() - Scalar::from(u64::MAX));
+    }
+
     #[allow(non_snake_case)]
     #[test]
     fn impl_mul() {
@@ -1337,6 +2157,10 @@ mod test {
         assert_eq!(z_prod, Scalar::from(60466176u64));
         assert_eq!(x_prod * y_prod, z_prod);
 
+        // Test that product works for owned scalars
+        let small = [2u64, 3, 4].iter().map(|&x| Scalar::from(x));
+        assert_eq!(small.product::<Scalar>(), Scalar::from(24u64));
+
     }
 
     #[test]
@@ -1379,6 +2203,36 @@ mod test {
         }
     }
 
+    #[test]
+    fn ct_comparisons() {
+        let cases = [
+            (Scalar::zero(), Scalar::one()),
+            (Scalar::from(0xffu64), Scalar::from(0x100u64)),
+            (Scalar::from(0x1_0000_0000u64), X),
+            (X, Y),
+            (Y, -Scalar::one()),
+        ];
+        for &(small, big) in cases.iter() {
+            assert_eq!(big.ct_gt(&small).unwrap_u8(), 1);
+            assert_eq!(small.ct_gt(&big).unwrap_u8(), 0);
+            assert_eq!(small.ct_lt(&big).unwrap_u8(), 1);
+            assert_eq!(big.ct_lt(&small).unwrap_u8(), 0);
+            assert_eq!(big.ct_ge(&small).unwrap_u8(), 1);
+            assert_eq!(small.ct_ge(&big).unwrap_u8(), 0);
+
+            assert_eq!(small.ct_gt(&small).unwrap_u8(), 0);
+            assert_eq!(small.ct_lt(&small).unwrap_u8(), 0);
+            assert_eq!(small.ct_ge(&small).unwrap_u8(), 1);
+        }
+
+        // Unreduced scalars compare by their reduced value: 2^255 - 1
+        // reduces to a value below l - 1.
+        let unreduced = Scalar::from_bits([0xff; 32]);
+        assert_eq!(unreduced.ct_lt(&-Scalar::one()).unwrap_u8(), 1);
+        assert_eq!(unreduced.ct_ge(&unreduced.reduce()).unwrap_u8(), 1);
+        assert_eq!(unreduced.ct_gt(&unreduced.reduce()).unwrap_u8(), 0);
+    }
+
     #[test]
     fn reduce() {
         let biggest = Scalar::from_bytes_mod_order([0xff; 32]);
@@ -1489,6 +2343,98 @@ mod test {
         assert!( Scalar::from_canonical_bytes(canonical_bytes).is_some() );
         assert!( Scalar::from_canonical_bytes(non_canonical_bytes_because_unreduced).is_none() );
         assert!( Scalar::from_canonical_bytes(non_canonical_bytes_because_highbit).is_none() );
+
+        let decoded = Scalar::ct_from_canonical_bytes(canonical_bytes);
+        assert_eq!(decoded.is_some().unwrap_u8(), 1);
+        assert_eq!(decoded.unwrap(), Scalar::from(1667457891u64));
+        assert_eq!(Scalar::ct_from_canonical_bytes(non_canonical_bytes_because_unreduced).is_some().unwrap_u8(), 0);
+        assert_eq!(Scalar::ct_from_canonical_bytes(non_canonical_bytes_because_highbit).is_some().unwrap_u8(), 0);
+
+        assert_eq!(Scalar::from_bits(canonical_bytes).ct_is_canonical().unwrap_u8(), 1);
+        assert_eq!(Scalar::from_bits(non_canonical_bytes_because_unreduced).ct_is_canonical().unwrap_u8(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "group")]
+    fn ff_prime_field_constants() {
+        use group::ff::{Field, PrimeField};
+
+        assert_eq!(<Scalar as Field>::ZERO, Scalar::zero());
+        assert_eq!(<Scalar as Field>::ONE, Scalar::one());
+        assert_eq!(Scalar::TWO_INV * Scalar::from(2u64), Scalar::one());
+
+        // ROOT_OF_UNITY has order 2^S.
+        let root = Scalar::ROOT_OF_UNITY;
+        assert_eq!(root * root * root * root, Scalar::one());
+        assert!(root * root != Scalar::one());
+        assert_eq!(root * Scalar::ROOT_OF_UNITY_INV, Scalar::one());
+
+        // ROOT_OF_UNITY = g^t and DELTA = g^(2^S).
+        let g = Scalar::MULTIPLICATIVE_GENERATOR;
+        let t = [
+            0x9604_98c6_973d_74fb,
+            0x0537_be77_a8bd_e735,
+            0x0000_0000_0000_0000,
+            0x0400_0000_0000_0000,
+        ];
+        assert_eq!(g.pow_vartime(&t), root);
+        assert_eq!(g.pow_vartime(&[1u64 << Scalar::S]), Scalar::DELTA);
+    }
+
+    #[test]
+    #[cfg(feature = "group")]
+    fn ff_prime_field_repr() {
+        use group::ff::PrimeField;
+
+        let x = Scalar::from_repr(X.to_bytes()).unwrap();
+        assert_eq!(x, X);
+        assert_eq!(x.to_repr(), X.to_bytes());
+        assert!(bool::from(X.is_odd() ^ X.is_even()));
+
+        // l itself is not a canonical representative
+        assert!(bool::from(Scalar::from_repr(constants::BASEPOINT_ORDER.bytes).is_none()));
+        assert!(Scalar::from_repr_vartime(constants::BASEPOINT_ORDER.bytes).is_none());
+        // neither is anything with the high bit set
+        let mut high_bit = [0u8; 32];
+        high_bit[31] = 0x80;
+        assert!(bool::from(Scalar::from_repr(high_bit).is_none()));
+    }
+
+    #[test]
+    #[cfg(feature = "group")]
+    fn ff_field_sqrt_and_invert() {
+        use group::ff::{Field, PrimeField};
+
+        for i in 1..32u64 {
+            let x = Scalar::from(i) * X;
+            let x2 = Field::square(&x);
+            let root = x2.sqrt().unwrap();
+            assert!(root == x || root == -x);
+
+            assert_eq!(Field::invert(&x).unwrap() * x, Scalar::one());
+        }
+        assert!(bool::from(Field::invert(&Scalar::zero()).is_none()));
+        assert_eq!(Scalar::zero().sqrt().unwrap(), Scalar::zero());
+
+        // The generator is not a square
+        assert!(bool::from(Scalar::MULTIPLICATIVE_GENERATOR.sqrt().is_none()));
+
+        let (is_square, root) = Scalar::sqrt_ratio(&Scalar::from(9u64), &Scalar::from(4u64));
+        assert!(bool::from(is_square));
+        assert_eq!(Field::square(&root) * Scalar::from(4u64), Scalar::from(9u64));
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize_scalar() {
+        use zeroize::{Zeroize, Zeroizing};
+
+        let mut x = X;
+        x.zeroize();
+        assert_eq!(x.bytes, [0u8; 32]);
+
+        let x = Zeroizing::new(X);
+        assert_eq!(*x, X);
     }
 
     #[test]
@@ -1500,14 +2446,110 @@ mod test {
         assert_eq!(parsed, X);
     }
 
-    #[cfg(debug_assertions)]
     #[test]
-    #[should_panic]
-    fn batch_invert_with_a_zero_input_panics() {
-        let mut xs = vec![Scalar::one(); 16];
-        xs[3] = Scalar::zero();
-        // This should panic in debug mode.
-        Scalar::batch_invert(&mut xs);
+    #[cfg(feature = "serde")]
+    fn serde_json_scalar_roundtrip() {
+        use serde_json;
+        let output = serde_json::to_string(&Scalar::from(0x0201u64)).unwrap();
+        assert_eq!(
+            output,
+            "\"0102000000000000000000000000000000000000000000000000000000000000\""
+        );
+        let output = serde_json::to_string(&X).unwrap();
+        let parsed: Scalar = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed, X);
+
+        // Non-canonical scalars are rejected, as for bytes.
+        let l_hex = "\"edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010\"";
+        assert!(serde_json::from_str::<Scalar>(l_hex).is_err());
+    }
+
+    #[test]
+    fn batch_invert_with_zero_inputs() {
+        let mut x = Scalar::from(3u64);
+        let mut v1: Vec<_> = (0..16).map(|_| {let tmp = x; x = x * x; tmp}).collect();
+        v1[0] = Scalar::zero();
+        v1[7] = Scalar::zero();
+        v1[15] = Scalar::zero();
+        let v2 = v1.clone();
+
+        let expected: Scalar = v1.iter().filter(|x| **x != Scalar::zero()).product();
+        let expected = expected.invert();
+        let ret = Scalar::batch_invert(&mut v1);
+        assert_eq!(ret, expected);
+
+        for (a, b) in v1.iter().zip(v2.iter()) {
+            if *b == Scalar::zero() {
+                assert_eq!(*a, Scalar::zero());
+            } else {
+                assert_eq!(a * b, Scalar::one());
+            }
+        }
+
+        let mut zeros = vec![Scalar::zero(); 4];
+        assert_eq!(Scalar::batch_invert(&mut zeros), Scalar::one());
+        assert!(zeros.iter().all(|x| *x == Scalar::zero()));
+    }
+
+    #[test]
+    fn square_and_pow() {
+        let x2 = X * X;
+        assert_eq!(X.square(), x2);
+        assert_eq!(X.pow(&Scalar::from(2u64)), x2);
+        assert_eq!(X.pow_vartime(&[2]), x2);
+
+        assert_eq!(X.pow(&Scalar::zero()), Scalar::one());
+        assert_eq!(X.pow(&Scalar::one()), X);
+        assert_eq!(Scalar::zero().pow(&X), Scalar::zero());
+        assert_eq!(X.pow_vartime(&[]), Scalar::one());
+
+        // x^(l-1) = 1 and x^(l-2) = 1/x
+        let l_minus_1 = -Scalar::one();
+        let l_minus_2 = l_minus_1 - Scalar::one();
+        assert_eq!(X.pow(&l_minus_1), Scalar::one());
+        assert_eq!(X.pow(&l_minus_2), X.invert());
+
+        // x^(a+b) = x^a * x^b
+        assert_eq!(X.pow(&(Y + X)), X.pow(&Y) * X.pow(&X));
+    }
+
+    #[test]
+    fn pow_vartime_matches_pow() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..8 {
+            let x = Scalar::random(&mut rng);
+            let e = Scalar::random(&mut rng);
+            let mut limbs = [0u64; 4];
+            for (i, limb) in limbs.iter_mut().enumerate() {
+                for j in 0..8 {
+                    *limb |= (e.bytes[8 * i + j] as u64) << (8 * j);
+                }
+            }
+            assert_eq!(x.pow_vartime(&limbs), x.pow(&e));
+        }
+
+        // Exponents longer than a Scalar
+        let x = Scalar::from(3u64);
+        assert_eq!(x.pow_vartime(&[1, 0, 0, 0, 0, 0]), x);
+    }
+
+    #[test]
+    fn from_bits_clamped() {
+        let s = Scalar::from_bits_clamped([0xff; 32]);
+        assert_eq!(s.bytes[0], 0b1111_1000);
+        assert_eq!(s.bytes[31], 0b0111_1111);
+        assert!(s.is_clamped());
+
+        let s = Scalar::from_bits_clamped([0x00; 32]);
+        assert_eq!(s.bytes[31], 0b0100_0000);
+        assert!(s.is_clamped());
+
+        // Clamping is idempotent
+        assert_eq!(Scalar::from_bits_clamped(s.bytes), s);
+
+        assert!(!Scalar::zero().is_clamped());
+        assert!(!Scalar::from_bits([0xff; 32]).is_clamped());
+        assert!(!Scalar::from_bits_clamped([0xff; 32]).reduce().is_clamped());
     }
 
     #[test]
@@ -1565,6 +2607,8 @@ mod test {
             .chain(iter::once(Scalar::from_bits([0xff; 32])));
 
         for scalar in cases {
+            test_pippenger_radix_iter(scalar, 4);
+            test_pippenger_radix_iter(scalar, 5);
             test_pippenger_radix_iter(scalar, 6);
             test_pippenger_radix_iter(scalar, 7);
             test_pippenger_radix_iter(scalar, 8);
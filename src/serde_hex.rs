@@ -0,0 +1,67 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Shared helpers for the serde encodings of 32-byte values.
+//!
+//! Scalars and Edwards points are serialized as lowercase hex strings for
+//! human-readable formats (such as JSON or TOML), and as raw bytes for
+//! binary formats (such as bincode).
+
+use core::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::Serializer;
+
+use hex;
+
+/// Displays 32 bytes as 64 lowercase hex digits, so that they can be
+/// serialized with `collect_str` without allocating.
+struct Hex<'a>(&'a [u8; 32]);
+
+impl<'a> fmt::Display for Hex<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialize `bytes` as a hex string if the serializer is
+/// human-readable, and as bytes otherwise.
+pub(crate) fn serialize<S>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Hex(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Drive `visitor` with a string if the deserializer is
+/// human-readable, and with bytes otherwise.
+///
+/// The visitor should handle strings by passing them through `decode`.
+pub(crate) fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where D: Deserializer<'de>, V: Visitor<'de>
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+/// Decode a string of 64 hex digits (in either case) into 32 bytes.
+pub(crate) fn decode<E>(s: &str, expected: &dyn de::Expected) -> Result<[u8; 32], E>
+    where E: de::Error
+{
+    if s.len() != 64 {
+        return Err(E::invalid_length(s.len(), expected));
+    }
+
+    hex::decode(s).ok_or_else(|| E::invalid_value(de::Unexpected::Str(s), expected))
+}
@@ -60,6 +60,13 @@ pub trait MultiscalarMul {
     ///
     /// It is an error to call this function with two iterators of different lengths.
     ///
+    /// The lengths are read from the iterators' `size_hint`s, which
+    /// must be exact, as they are for every `ExactSizeIterator`.  The
+    /// inputs therefore need not be collected into a `Vec` first: a
+    /// lazily computed iterator such as `(0..n).map(..)` can be passed
+    /// directly.  Each iterator is consumed in a single pass, and is
+    /// never cloned.
+    ///
     /// # Examples
     ///
     /// The trait bound aims for maximum flexibility: the inputs must be
@@ -175,6 +182,11 @@ pub trait VartimeMultiscalarMul {
     ///
     /// It is an error to call this function with two iterators of different lengths.
     ///
+    /// As with `MultiscalarMul::multiscalar_mul`, the iterators'
+    /// `size_hint`s must be exact, as they are for every
+    /// `ExactSizeIterator`, and each iterator is consumed once, so
+    /// lazily computed inputs need not be collected first.
+    ///
     /// # Examples
     ///
     /// The trait bound aims for maximum flexibility: the inputs must be
@@ -9,6 +9,41 @@
 // - Henry de Valence <hdevalence@hdevalence.ca>
 
 //! Code for fixed- and sliding-window functionality
+//!
+//! The `LookupTable` types hold the multiples \\(P, 2P, \ldots, NP\\)
+//! of a point \\(P\\), and their `select` method returns \\(xP\\) for
+//! \\(-N \leq x \leq N\\) in constant time, by scanning every entry of
+//! the table.  They can be used to build constant-time windowed
+//! scalar multiplication over a custom base.
+//!
+//! # Example
+//!
+//! Compute \\([s]P\\) using the radix-16 digits of \\(s\\):
+//!
+//! ```
+//! use curve25519_dalek::constants;
+//! use curve25519_dalek::edwards::EdwardsPoint;
+//! use curve25519_dalek::scalar::Scalar;
+//! use curve25519_dalek::traits::Identity;
+//! use curve25519_dalek::window::LookupTable;
+//!
+//! let P = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234u64);
+//! let s = Scalar::from(987654321u64);
+//!
+//! // Holds P, 2P, ..., 8P
+//! let table = LookupTable::<EdwardsPoint>::from(&P);
+//!
+//! // Digits -8 <= d_i < 8 with s = d_0 + d_1 16 + ... + d_63 16^63
+//! let digits = s.to_radix_2w(4);
+//!
+//! let mut Q = EdwardsPoint::identity();
+//! for d in digits.iter().rev() {
+//!     Q = Q * Scalar::from(16u64);
+//!     Q += table.select(*d);
+//! }
+//!
+//! assert_eq!(Q, P * s);
+//! ```
 
 #![allow(non_snake_case)]
 
@@ -25,56 +60,43 @@ use edwards::EdwardsPoint;
 use backend::serial::curve_models::ProjectiveNielsPoint;
 use backend::serial::curve_models::AffineNielsPoint;
 
+macro_rules! impl_lookup_table {
+    (Name = $name:ident, Size = $size:expr, SizeNeg = $neg:expr, SizeRange = $range:expr, ConversionRange = $conv_range:expr) => {
+
 /// A lookup table of precomputed multiples of a point \\(P\\), used to
-/// compute \\( xP \\) for \\( -8 \leq x \leq 8 \\).
+/// compute \\( xP \\) for \\( -N \leq x \leq N \\), where \\(N\\) is
+/// the size of the table.
 ///
 /// The computation of \\( xP \\) is done in constant time by the `select` function.
 ///
-/// Since `LookupTable` does not implement `Index`, it's more difficult
-/// to accidentally use the table directly.  Unfortunately the table is
-/// only `pub(crate)` so that we can write hardcoded constants, so it's
-/// still technically possible.  It would be nice to prevent direct
-/// access to the table.
+/// Since the table does not implement `Index`, it's more difficult
+/// to accidentally use the table directly.  Its contents are only
+/// `pub(crate)` so that we can write hardcoded constants.
 ///
-/// XXX make this generic with respect to table size
+/// A table of `EdwardsPoint`s can be constructed from any point with
+/// `From<&EdwardsPoint>`.
 #[derive(Copy, Clone)]
-pub struct LookupTable<T>(pub(crate) [T; 8]);
-
-use clear_on_drop::clear::ZeroSafe;
-
-/// This type isn't actually zeroable (all zero bytes are not valid
-/// points), but we want to be able to use `clear_on_drop` to erase slices
-/// of `LookupTable`.
-///
-/// Since the `ZeroSafe` trait is only used by `clear_on_drop`, the only
-/// situation where this would be a problem is if code attempted to use
-/// a `ClearOnDrop` to erase a `LookupTable` and then used the table
-/// afterwards.
-///
-/// Normally this is not a problem, since the table's storage is usually
-/// dropped too.
-///
-/// XXX is this a good compromise?
-unsafe impl<T> ZeroSafe for LookupTable<T> {}
+pub struct $name<T>(pub(crate) [T; $size]);
 
-impl<T> LookupTable<T>
+impl<T> $name<T>
 where
     T: Identity + ConditionallySelectable + ConditionallyNegatable,
 {
-    /// Given \\(-8 \leq x \leq 8\\), return \\(xP\\) in constant time.
+    /// Given \\(-N \leq x \leq N\\), return \\(xP\\) in constant time.
     pub fn select(&self, x: i8) -> T {
-        debug_assert!(x >= -8);
-        debug_assert!(x <= 8);
+        debug_assert!(x as i16 >= $neg as i16);
+        debug_assert!(x as i16 <= $size as i16);
 
-        // Compute xabs = |x|
-        let xmask = x >> 7;
-        let xabs = (x + xmask) ^ xmask;
+        // Compute xabs = |x|.  Widen to i16 so that x = -128 is
+        // handled correctly for the largest table size.
+        let xmask = x as i16 >> 7;
+        let xabs = (x as i16 + xmask) ^ xmask;
 
         // Set t = 0 * P = identity
         let mut t = T::identity();
-        for j in 1..9 {
+        for j in $range {
             // Copy `points[j-1] == j*P` onto `t` in constant time if `|x| == j`.
-            let c = (xabs as u8).ct_eq(&(j as u8));
+            let c = (xabs as u16).ct_eq(&(j as u16));
             t.conditional_assign(&self.0[j - 1], c);
         }
         // Now t == |x| * P.
@@ -87,39 +109,75 @@ where
     }
 }
 
-impl<T: Copy + Default> Default for LookupTable<T> {
-    fn default() -> LookupTable<T> {
-        LookupTable([T::default(); 8])
+impl<T: Copy + Default> Default for $name<T> {
+    fn default() -> $name<T> {
+        $name([T::default(); $size])
     }
 }
 
-impl<T: Debug> Debug for LookupTable<T> {
+impl<T: Debug> Debug for $name<T> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "LookupTable({:?})", self.0)
+        write!(f, "{}({:?})", stringify!($name), self.0)
     }
 }
 
-impl<'a> From<&'a EdwardsPoint> for LookupTable<ProjectiveNielsPoint> {
+impl<'a> From<&'a EdwardsPoint> for $name<EdwardsPoint> {
     fn from(P: &'a EdwardsPoint) -> Self {
-        let mut points = [P.to_projective_niels(); 8];
-        for j in 0..7 {
+        let mut points = [*P; $size];
+        for j in $conv_range {
+            points[j + 1] = P + &points[j];
+        }
+        $name(points)
+    }
+}
+
+impl<'a> From<&'a EdwardsPoint> for $name<ProjectiveNielsPoint> {
+    fn from(P: &'a EdwardsPoint) -> Self {
+        let mut points = [P.to_projective_niels(); $size];
+        for j in $conv_range {
             points[j + 1] = (P + &points[j]).to_extended().to_projective_niels();
         }
-        LookupTable(points)
+        $name(points)
     }
 }
 
-impl<'a> From<&'a EdwardsPoint> for LookupTable<AffineNielsPoint> {
+impl<'a> From<&'a EdwardsPoint> for $name<AffineNielsPoint> {
     fn from(P: &'a EdwardsPoint) -> Self {
-        let mut points = [P.to_affine_niels(); 8];
+        let mut points = [P.to_affine_niels(); $size];
         // XXX batch inversion would be good if perf mattered here
-        for j in 0..7 {
+        for j in $conv_range {
             points[j + 1] = (P + &points[j]).to_extended().to_affine_niels()
         }
-        LookupTable(points)
+        $name(points)
     }
 }
 
+}} // End macro_rules! impl_lookup_table
+
+// The first one has to be named "LookupTable" because it's used as a constructor for consts.
+impl_lookup_table! {Name = LookupTable,         Size =   8, SizeNeg =   -8, SizeRange = 1 ..   9, ConversionRange = 0 ..   7} // radix-16
+impl_lookup_table! {Name = LookupTableRadix32,  Size =  16, SizeNeg =  -16, SizeRange = 1 ..  17, ConversionRange = 0 ..  15} // radix-32
+impl_lookup_table! {Name = LookupTableRadix64,  Size =  32, SizeNeg =  -32, SizeRange = 1 ..  33, ConversionRange = 0 ..  31} // radix-64
+impl_lookup_table! {Name = LookupTableRadix128, Size =  64, SizeNeg =  -64, SizeRange = 1 ..  65, ConversionRange = 0 ..  63} // radix-128
+impl_lookup_table! {Name = LookupTableRadix256, Size = 128, SizeNeg = -128, SizeRange = 1 .. 129, ConversionRange = 0 .. 127} // radix-256
+
+use clear_on_drop::clear::ZeroSafe;
+
+/// This type isn't actually zeroable (all zero bytes are not valid
+/// points), but we want to be able to use `clear_on_drop` to erase slices
+/// of `LookupTable`.
+///
+/// Since the `ZeroSafe` trait is only used by `clear_on_drop`, the only
+/// situation where this would be a problem is if code attempted to use
+/// a `ClearOnDrop` to erase a `LookupTable` and then used the table
+/// afterwards.
+///
+/// Normally this is not a problem, since the table's storage is usually
+/// dropped too.
+///
+/// XXX is this a good compromise?
+unsafe impl<T> ZeroSafe for LookupTable<T> {}
+
 /// Holds odd multiples 1A, 3A, ..., 15A of a point A.
 #[derive(Copy, Clone)]
 pub(crate) struct NafLookupTable5<T>(pub(crate) [T; 8]);
@@ -210,3 +268,25 @@ impl<'a> From<&'a EdwardsPoint> for NafLookupTable8<AffineNielsPoint> {
         NafLookupTable8(Ai)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use constants;
+    use scalar::Scalar;
+
+    #[test]
+    fn edwards_lookup_table_select() {
+        let P = constants::ED25519_BASEPOINT_POINT + constants::EIGHT_TORSION[1];
+        let table = LookupTable::<EdwardsPoint>::from(&P);
+        let radix256_table = LookupTableRadix256::<EdwardsPoint>::from(&P);
+
+        for x in -8i8..=8 {
+            let expected = P * Scalar::from(x.unsigned_abs() as u64);
+            let expected = if x < 0 { -expected } else { expected };
+            assert_eq!(table.select(x), expected);
+            assert_eq!(radix256_table.select(x), expected);
+        }
+        assert_eq!(radix256_table.select(-128), -(P * Scalar::from(128u64)));
+    }
+}